@@ -1,5 +1,5 @@
 // Standards ─────────────────────────────────────────────────────
-use std::{collections::HashMap, io::stdout};
+use std::{collections::HashMap, io::stdout, time::{Duration, Instant}};
 
 // Crates ────────────────────────────────────────────────────────
 use rusqlite::Connection;
@@ -18,11 +18,13 @@ use ratatui::{
 // mods ──────────────────────────────────────────────────────────
 use super::{
     components::{footer, header, modal, search, section, title},
-    structs::{Component, Filter, InputField, Modal, SectionState, SuggestionState},
+    structs::{
+        Component, DaySelector, Filter, InputField, Modal, SectionState, Sort, SuggestionState,
+    },
 };
 use crate::{
-    consts::{DAILY, JOURNAL, LOG, REAL_TIME, WEEKLY},
-    structs::{Job, Log, Stat},
+    consts::{DAILY, JOURNAL, LOG, MOUNT, REAL_TIME, WATCHER_REFRESH_MS, WEEKLY},
+    structs::{Heartbeat, Job, Log, SchedulePolicy, Stat},
 };
 
 // App ───────────────────────────────────────────────────────────
@@ -34,15 +36,28 @@ pub struct App {
     pub jobs: HashMap<&'static str, Vec<Job>>,
     pub stats: HashMap<&'static str, Stat>,
     pub logs: Vec<Log>,
+    pub policies: Vec<SchedulePolicy>,
+    pub heartbeat: Option<Heartbeat>,
+    pub watcher_refreshed_at: Option<Instant>,
 
     pub search: InputField,
     pub filter: Filter,
     pub filter_clicked: bool,
+    pub sort: Sort,
+    pub sort_clicked: bool,
 
     pub source: InputField,
     pub target: InputField,
-    pub hour: InputField,
-    pub day: InputField,
+    pub time: InputField,
+    pub days: DaySelector,
+    pub retries: InputField,
+    pub delay: InputField,
+    pub backoff: InputField,
+    pub timeout: InputField,
+    pub trigger: InputField,
+    pub once_per: InputField,
+    pub backend: InputField,
+    pub interval: InputField,
 
     pub suggestion_state: SuggestionState,
 
@@ -69,13 +84,26 @@ impl App {
             jobs: HashMap::new(),
             stats: HashMap::new(),
             logs: Vec::new(),
+            policies: Vec::new(),
+            heartbeat: None,
+            watcher_refreshed_at: None,
             search: InputField::default(),
             filter: Filter::default(),
             filter_clicked: false,
+            sort: Sort::default(),
+            sort_clicked: false,
             source: InputField::default(),
             target: InputField::default(),
-            hour: InputField::default(),
-            day: InputField::default(),
+            time: InputField::default(),
+            days: DaySelector::default(),
+            retries: InputField::default(),
+            delay: InputField::default(),
+            backoff: InputField::default(),
+            timeout: InputField::default(),
+            trigger: InputField::default(),
+            once_per: InputField::default(),
+            backend: InputField::default(),
+            interval: InputField::default(),
             suggestion_state: SuggestionState::default(),
             to_replace: InputField::default(),
             replace_with: InputField::default(),
@@ -96,14 +124,17 @@ impl App {
         jobs: HashMap<&'static str, Vec<Job>>,
         logs: Vec<Log>,
         stats: HashMap<&'static str, Stat>,
+        policies: Vec<SchedulePolicy>,
     ) -> Result<()> {
         // Assign records
         self.jobs = jobs;
         self.logs = logs;
+        self.policies = policies;
         self.stats = stats;
+        self.sort_jobs();
 
         // Assign tables states
-        self.states = HashMap::with_capacity(5);
+        self.states = HashMap::with_capacity(6);
         self.states.insert(
             REAL_TIME,
             SectionState::new(self.stats.get(REAL_TIME).unwrap().count as usize),
@@ -116,6 +147,10 @@ impl App {
             WEEKLY,
             SectionState::new(self.stats.get(WEEKLY).unwrap().count as usize),
         );
+        self.states.insert(
+            MOUNT,
+            SectionState::new(self.stats.get(MOUNT).unwrap().count as usize),
+        );
         self.states
             .insert(JOURNAL, SectionState::new(self.logs.len()));
         self.states.insert(LOG, SectionState::new(0));
@@ -125,6 +160,7 @@ impl App {
         // Init
 
         while !self.exit {
+            self.refresh_watcher();
             terminal.draw(|frame| self.draw(frame))?;
             self.handle_events().wrap_err("Handle events failed")?;
         }
@@ -137,6 +173,11 @@ impl App {
     }
 
     fn handle_events(&mut self) -> Result<()> {
+        // Redraw now and then to keep the watcher status live
+        if !event::poll(Duration::from_millis(WATCHER_REFRESH_MS))? {
+            return Ok(());
+        }
+
         match event::read()? {
             Event::Key(event) if event.kind == KeyEventKind::Press => self
                 .handle_key(event)
@@ -170,7 +211,7 @@ impl Widget for &mut App {
         ] = vertical_layout.areas(area);
 
         title(title_area, buf);
-        header(header_area, buf, &self.stats);
+        header(header_area, buf, &self.stats, &self.policies, self.heartbeat.as_ref());
         search(search_area, buf, self);
         section(section_area, buf, self);
         modal(area, buf, self);
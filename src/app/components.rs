@@ -2,12 +2,13 @@
 use std::{collections::HashMap, vec};
 
 // Crates ────────────────────────────────────────────────────────
+use chrono::Local;
 use crossterm::event::MouseEvent;
 use ratatui::{
     layout::{Alignment, Constraint, Flex, Layout, Position},
     prelude::{Buffer, Rect},
     style::{Color, Modifier, Style, Stylize},
-    text::{Line, Text},
+    text::{Line, Span, Text},
     widgets::{
         Block, BorderType, Cell, Clear, List, ListItem, Padding, Paragraph, Row, Scrollbar,
         ScrollbarOrientation, StatefulWidget, Table, Widget,
@@ -17,24 +18,28 @@ use ratatui::{
 // mods ──────────────────────────────────────────────────────────
 use super::{
     app::App,
-    structs::{Component, Filter, Modal},
+    structs::{Component, DaySelector, Filter, Modal},
 };
 use crate::{
     consts::{
-        ACTION_ACTIVE, ACTION_BACKUP, ACTION_CLONE, ACTION_CLOSE, ACTION_DELETE, ACTION_DISABLE,
-        ACTION_EDIT, ACTION_ENABLE, ACTION_ERASE, ACTION_LOGS, ACTION_MIRROR, ACTION_MOVE,
-        ACTION_NEW, ACTION_QUIT, ACTION_UPDATE, ACTION_VIEW, ACTIVE, ACTIVE_SLIDER, APP_SUBTITLE,
-        APP_TITLE, ARROW_DOWN, ARROW_UP, COL_BEIGE, COL_BLUE, COL_BORDER, COL_GRAY, COL_GREEN,
-        COL_LBROWN, COL_MAGENTA, COL_ORANGE, COL_PURPLE, COL_TITLE, DAILY, DAY, EMOJI_FILTER,
-        EMOJI_SEARCH, EMOJI_SECTION, EMOJI_STATS, EMOJI_STATUS_FAILED, EMOJI_STATUS_OTHER,
-        EMOJI_STATUS_PARTIAL, EMOJI_STATUS_SUCCESS, FAILED, FILTER, HOUR, INACTIVE, JOURNAL, LOG,
-        PARTIAL, REAL_TIME, REPLACE, REPLACE_WITH, SEARCH, SEPARATOR, SHORTCUT_DAILY,
-        SHORTCUT_FILTER, SHORTCUT_REAL_TIME, SHORTCUT_SEARCH, SHORTCUT_WEEKLY, SLIDER, SOURCE,
-        SUCCESS, TARGET, TO_REPLACE, WEEKLY,
+        ACTION_ACTIVE, ACTION_BACKUP, ACTION_CANCEL, ACTION_CLONE, ACTION_CLOSE, ACTION_DAYS,
+        ACTION_DELETE, ACTION_DISABLE, ACTION_EDIT, ACTION_ENABLE, ACTION_ERASE, ACTION_GUARD,
+        ACTION_LOGS, ACTION_MIRROR, ACTION_MOVE, ACTION_NEW, ACTION_QUIT, ACTION_TOGGLE_DAY,
+        ACTION_UPDATE, ACTION_VIEW, ACTIVE, ACTIVE_SLIDER, ALIVE, APP_SUBTITLE, APP_TITLE,
+        ARROW_DOWN, ARROW_UP, BACKEND, BACKOFF, BLACKOUT, COL_BEIGE, COL_BLUE, COL_BORDER,
+        COL_GRAY, COL_GREEN, COL_LBROWN, COL_MAGENTA, COL_ORANGE, COL_PURPLE, COL_TITLE, DAILY,
+        DAYS, DELAY, DOWN, DUE_FORMAT, EMOJI_BLACKOUT, EMOJI_DOWN, EMOJI_FILTER, EMOJI_PAUSED,
+        EMOJI_RUNNING, EMOJI_SEARCH, EMOJI_SECTION, EMOJI_SORT, EMOJI_STALE, EMOJI_STATS,
+        EMOJI_WATCHING, FILTER, INACTIVE, INTERVAL, JOURNAL, LOG, MOUNT, ONCE_PER, PAUSE, PAUSED,
+        REAL_TIME, REPLACE, REPLACE_WITH, RETRIES, RUNNING, SCHEDULE, SEARCH, SEPARATOR,
+        SHORTCUT_DAILY, SHORTCUT_FILTER, SHORTCUT_MOUNT, SHORTCUT_REAL_TIME, SHORTCUT_SEARCH,
+        SHORTCUT_SORT, SHORTCUT_WEEKLY, SLIDER, SORT, SOURCE, STALE, TARGET, TIME, TIMEOUT,
+        TO_REPLACE, TRIGGER, WATCHER, WEEK_DAYS, WEEKLY,
     },
-    structs::Stat,
+    structs::{Heartbeat, SchedulePolicy, Stat},
     utils::{
-        capitalise, field, get_active_jobs, get_active_logs, get_columns_info_by_key, into_lines,
+        blocked_until, capitalise, field, get_active_jobs, get_active_logs,
+        get_columns_info_by_key, into_lines, log_status_emoji, watcher_health,
     },
 };
 
@@ -49,13 +54,115 @@ pub fn title(area: Rect, buf: &mut Buffer) {
 }
 
 // Header ────────────────────────────────────────────────────────
-pub fn header(area: Rect, buf: &mut Buffer, stats: &HashMap<&'static str, Stat>) {
-    let horizotal_layout = Layout::horizontal(vec![Constraint::Fill(1); 3]);
-    let [real_time_area, daily_area, weekly_area] = horizotal_layout.areas(area);
+pub fn header(
+    area: Rect,
+    buf: &mut Buffer,
+    stats: &HashMap<&'static str, Stat>,
+    policies: &[SchedulePolicy],
+    heartbeat: Option<&Heartbeat>,
+) {
+    let horizotal_layout = Layout::horizontal(vec![Constraint::Fill(1); 6]);
+    let [
+        real_time_area,
+        daily_area,
+        weekly_area,
+        mount_area,
+        schedule_area,
+        watcher_area,
+    ] = horizotal_layout.areas(area);
 
     card(real_time_area, buf, stats.get(REAL_TIME).unwrap());
     card(daily_area, buf, stats.get(DAILY).unwrap());
     card(weekly_area, buf, stats.get(WEEKLY).unwrap());
+    card(mount_area, buf, stats.get(MOUNT).unwrap());
+    schedule_card(schedule_area, buf, policies);
+    watcher_card(watcher_area, buf, heartbeat);
+}
+
+// Whether scheduled backups are running, paused or in a blackout window
+fn schedule_card(area: Rect, buf: &mut Buffer, policies: &[SchedulePolicy]) {
+    let block = Block::bordered()
+        .padding(Padding::new(1, 1, 0, 0))
+        .border_style(COL_BORDER)
+        .border_type(BorderType::Rounded);
+    block.clone().render(area, buf);
+
+    let vertical_layout = Layout::vertical(vec![Constraint::Fill(1); 3]);
+    let [top, middle, bottom] = vertical_layout.areas(block.inner(area));
+
+    Text::from(format!("{} {}", EMOJI_STATS, SCHEDULE))
+        .add_modifier(Modifier::BOLD)
+        .fg(COL_TITLE)
+        .render(top, buf);
+
+    let now = Local::now().naive_local();
+    let until = blocked_until(policies, &now);
+    let is_paused = policies
+        .iter()
+        .any(|policy| policy.kind == PAUSE && policy.blocks_until(&now).is_some());
+
+    let (state, color) = match until {
+        Some(_) if is_paused => (format!("{} {}", EMOJI_PAUSED, PAUSED), COL_ORANGE),
+        Some(_) => (format!("{} {}", EMOJI_BLACKOUT, BLACKOUT), COL_ORANGE),
+        None => (format!("{} {}", EMOJI_RUNNING, RUNNING), COL_GREEN),
+    };
+
+    Text::from(state)
+        .add_modifier(Modifier::BOLD)
+        .fg(color)
+        .render(middle, buf);
+
+    if let Some(until) = until {
+        Text::from(format!("until {}", until.format(DUE_FORMAT)))
+            .fg(COL_GRAY)
+            .render(bottom, buf);
+    }
+}
+
+// Whether syncrab_w is alive, stale or down, from its heartbeat
+fn watcher_card(area: Rect, buf: &mut Buffer, heartbeat: Option<&Heartbeat>) {
+    let block = Block::bordered()
+        .padding(Padding::new(1, 1, 0, 0))
+        .border_style(COL_BORDER)
+        .border_type(BorderType::Rounded);
+    block.clone().render(area, buf);
+
+    let vertical_layout = Layout::vertical(vec![Constraint::Fill(1); 3]);
+    let [top, middle, bottom] = vertical_layout.areas(block.inner(area));
+
+    Text::from(format!("{} {}", EMOJI_STATS, WATCHER))
+        .add_modifier(Modifier::BOLD)
+        .fg(COL_TITLE)
+        .render(top, buf);
+
+    let health = watcher_health(heartbeat, &Local::now().naive_local());
+    Text::from(formatted_health(health))
+        .add_modifier(Modifier::BOLD)
+        .fg(match health {
+            ALIVE => COL_GREEN,
+            STALE => COL_ORANGE,
+            _ => COL_GRAY,
+        })
+        .render(middle, buf);
+
+    if let Some(heartbeat) = heartbeat.filter(|_| health != DOWN) {
+        Text::from(format!(
+            "{} jobs, last event {}",
+            heartbeat.watched_count,
+            heartbeat.last_event_at.as_deref().unwrap_or("-")
+        ))
+        .fg(COL_GRAY)
+        .render(bottom, buf);
+    }
+}
+
+fn formatted_health(health: &str) -> String {
+    let emoji = match health {
+        ALIVE => EMOJI_WATCHING,
+        STALE => EMOJI_STALE,
+        _ => EMOJI_DOWN,
+    };
+    format!("{} {}", emoji, health)
 }
 
 fn card(area: Rect, buf: &mut Buffer, stat: &Stat) {
@@ -92,9 +199,12 @@ fn card(area: Rect, buf: &mut Buffer, stat: &Stat) {
 
 // Search ────────────────────────────────────────────────────────
 pub fn search(area: Rect, buf: &mut Buffer, app: &mut App) {
-    let horizontal_layout =
-        Layout::horizontal(vec![Constraint::Ratio(1, 1), Constraint::Ratio(1, 3)]);
-    let [left, right] = horizontal_layout.areas(area);
+    let horizontal_layout = Layout::horizontal(vec![
+        Constraint::Ratio(1, 1),
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+    ]);
+    let [left, middle, right] = horizontal_layout.areas(area);
 
     let mut search_style = (COL_TITLE, COL_BORDER);
 
@@ -104,9 +214,12 @@ pub fn search(area: Rect, buf: &mut Buffer, app: &mut App) {
 
             if left.contains(pos) {
                 search_style = enable_search(app, left, buf);
-            } else if right.contains(pos) && !app.filter_clicked {
+            } else if middle.contains(pos) && !app.filter_clicked {
                 app.filter_clicked = true;
                 app.filter = app.filter.next();
+            } else if right.contains(pos) && !app.sort_clicked {
+                app.sort_clicked = true;
+                app.next_sort();
             }
         } else if app.active_component == Some(Component::Search) {
             search_style = enable_search(app, left, buf);
@@ -137,6 +250,14 @@ pub fn search(area: Rect, buf: &mut Buffer, app: &mut App) {
             COL_TITLE,
             COL_BORDER,
         ))
+        .render(middle, buf);
+
+    Paragraph::new(capitalise(&app.sort.to_string()))
+        .block(field(
+            &format!("{} [{}] {}", EMOJI_SORT, SHORTCUT_SORT, capitalise(SORT)),
+            COL_TITLE,
+            COL_BORDER,
+        ))
         .render(right, buf);
 }
 
@@ -155,12 +276,14 @@ pub fn section(area: Rect, buf: &mut Buffer, app: &mut App) {
             Constraint::Fill(1),
             Constraint::Fill(1),
             Constraint::Fill(1),
+            Constraint::Fill(1),
         ]);
-        let [real_time_area, daily_area, weekly_area] = vertical_layout.areas(area);
+        let [real_time_area, daily_area, weekly_area, mount_area] = vertical_layout.areas(area);
 
         block(real_time_area, buf, REAL_TIME, SHORTCUT_REAL_TIME, app);
         block(daily_area, buf, DAILY, SHORTCUT_DAILY, app);
         block(weekly_area, buf, WEEKLY, SHORTCUT_WEEKLY, app);
+        block(mount_area, buf, MOUNT, SHORTCUT_MOUNT, app);
     }
 }
 
@@ -206,7 +329,24 @@ fn block(area: Rect, buf: &mut Buffer, freq: &str, shortcut: char, app: &mut App
     let vertical_layout = Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]);
     let [top, bottom] = vertical_layout.areas(block.inner(area));
 
-    Text::from(format!("{} [{}] {}", EMOJI_SECTION, shortcut, name))
+    // Real-time jobs only sync while syncrab_w runs
+    let title = match freq {
+        REAL_TIME => {
+            let health = watcher_health(app.heartbeat.as_ref(), &Local::now().naive_local());
+            format!(
+                "{} [{}] {}{}{} {}",
+                EMOJI_SECTION,
+                shortcut,
+                name,
+                SEPARATOR,
+                WATCHER.to_lowercase(),
+                formatted_health(health)
+            )
+        }
+        _ => format!("{} [{}] {}", EMOJI_SECTION, shortcut, name),
+    };
+
+    Text::from(title)
         .add_modifier(Modifier::BOLD)
         .fg(COL_TITLE)
         .render(top, buf);
@@ -365,9 +505,9 @@ pub fn form(area: Rect, buf: &mut Buffer, app: &mut App) {
     if let Some(job) = &app.selected_job {
         form_name = &job.frequency;
         if &job.frequency == REAL_TIME {
-            fields_num = 2;
-        } else {
             fields_num = 3;
+        } else {
+            fields_num = 4;
         }
     } else if app.active_modal == Some(Modal::Replace) {
         fields_num = 2;
@@ -381,31 +521,110 @@ pub fn form(area: Rect, buf: &mut Buffer, app: &mut App) {
         3 => Layout::vertical(vec![Constraint::Ratio(1, 3); 3])
             .areas::<3>(area)
             .to_vec(),
+        4 => Layout::vertical(vec![Constraint::Ratio(1, 4); 4])
+            .areas::<4>(area)
+            .to_vec(),
         _ => unreachable!(),
     };
 
+    let mut days_area: Option<Rect> = None;
+
     let (areas, labels, mut components): (Vec<_>, Vec<_>, Vec<_>) = match form_name {
-        REAL_TIME => (
-            vec![vertical_areas[0], vertical_areas[1]],
-            vec![SOURCE, TARGET],
-            vec![&mut app.source, &mut app.target],
-        ),
-        DAILY => (
-            vec![vertical_areas[0], vertical_areas[1], vertical_areas[2]],
-            vec![SOURCE, TARGET, HOUR],
-            vec![&mut app.source, &mut app.target, &mut app.hour],
-        ),
+        REAL_TIME => {
+            let [backend, interval] =
+                Layout::horizontal(vec![Constraint::Ratio(1, 2); 2]).areas(vertical_areas[2]);
+            (
+                vec![vertical_areas[0], vertical_areas[1], backend, interval],
+                vec![SOURCE, TARGET, BACKEND, INTERVAL],
+                vec![
+                    &mut app.source,
+                    &mut app.target,
+                    &mut app.backend,
+                    &mut app.interval,
+                ],
+            )
+        }
+        DAILY => {
+            let [retries, delay, backoff, timeout] =
+                Layout::horizontal(vec![Constraint::Ratio(1, 4); 4]).areas(vertical_areas[3]);
+            (
+                vec![
+                    vertical_areas[0],
+                    vertical_areas[1],
+                    vertical_areas[2],
+                    retries,
+                    delay,
+                    backoff,
+                    timeout,
+                ],
+                vec![SOURCE, TARGET, TIME, RETRIES, DELAY, BACKOFF, TIMEOUT],
+                vec![
+                    &mut app.source,
+                    &mut app.target,
+                    &mut app.time,
+                    &mut app.retries,
+                    &mut app.delay,
+                    &mut app.backoff,
+                    &mut app.timeout,
+                ],
+            )
+        }
         WEEKLY => {
             let horizontal_layout = Layout::horizontal(vec![Constraint::Ratio(1, 2); 2]);
             let [left, right] = horizontal_layout.areas(vertical_areas[2]);
+            let [retries, delay, backoff, timeout] =
+                Layout::horizontal(vec![Constraint::Ratio(1, 4); 4]).areas(vertical_areas[3]);
+            days_area = Some(right);
+            (
+                vec![
+                    vertical_areas[0],
+                    vertical_areas[1],
+                    left,
+                    retries,
+                    delay,
+                    backoff,
+                    timeout,
+                ],
+                vec![SOURCE, TARGET, TIME, RETRIES, DELAY, BACKOFF, TIMEOUT],
+                vec![
+                    &mut app.source,
+                    &mut app.target,
+                    &mut app.time,
+                    &mut app.retries,
+                    &mut app.delay,
+                    &mut app.backoff,
+                    &mut app.timeout,
+                ],
+            )
+        }
+        MOUNT => {
+            let horizontal_layout = Layout::horizontal(vec![Constraint::Ratio(1, 2); 2]);
+            let [left, right] = horizontal_layout.areas(vertical_areas[2]);
+            let [retries, delay, backoff, timeout] =
+                Layout::horizontal(vec![Constraint::Ratio(1, 4); 4]).areas(vertical_areas[3]);
             (
-                vec![vertical_areas[0], vertical_areas[1], left, right],
-                vec![SOURCE, TARGET, HOUR, DAY],
+                vec![
+                    vertical_areas[0],
+                    vertical_areas[1],
+                    left,
+                    right,
+                    retries,
+                    delay,
+                    backoff,
+                    timeout,
+                ],
+                vec![
+                    SOURCE, TARGET, TRIGGER, ONCE_PER, RETRIES, DELAY, BACKOFF, TIMEOUT,
+                ],
                 vec![
                     &mut app.source,
                     &mut app.target,
-                    &mut app.hour,
-                    &mut app.day,
+                    &mut app.trigger,
+                    &mut app.once_per,
+                    &mut app.retries,
+                    &mut app.delay,
+                    &mut app.backoff,
+                    &mut app.timeout,
                 ],
             )
         }
@@ -430,6 +649,24 @@ pub fn form(area: Rect, buf: &mut Buffer, app: &mut App) {
                 break;
             }
         }
+
+        if let Some(area) = days_area.filter(|area| area.contains(pos)) {
+            app.active_component = Some(Component::Days);
+            // Walk the spans day_selector renders, right after the border and the padding
+            let mut x = area.x + 2;
+            for (i, span) in day_spans(&app.days, false).iter().enumerate() {
+                let width = span.width() as u16;
+                if (x..x + width).contains(&column) {
+                    // Odd spans are the separators between days
+                    if i % 2 == 0 {
+                        app.days.toggle(i / 2);
+                    }
+                    break;
+                }
+                x += width;
+            }
+            app.event = None;
+        }
     } else if let Some(comp) = app.active_component.as_ref() {
         for (i, label) in labels.iter().enumerate() {
             if comp == &Component::from_str(label) {
@@ -514,6 +751,39 @@ pub fn form(area: Rect, buf: &mut Buffer, app: &mut App) {
             StatefulWidget::render(list, area, buf, &mut app.suggestion_state.state);
         }
     }
+
+    if let Some(area) = days_area {
+        let is_active = app.active_component == Some(Component::Days);
+        day_selector(area, buf, &app.days, is_active);
+    }
+}
+
+// Each day, followed by a separator
+fn day_spans(days: &DaySelector, is_active: bool) -> Vec<Span<'static>> {
+    WEEK_DAYS
+        .iter()
+        .enumerate()
+        .map(|(i, day)| {
+            let mut style = if days.selected[i] {
+                Style::default().fg(COL_GREEN).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(COL_GRAY)
+            };
+            if is_active && days.index == i {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            Span::styled(format!(" {} ", day), style)
+        })
+        .flat_map(|span| [span, Span::raw(" ")])
+        .collect()
+}
+
+fn day_selector(area: Rect, buf: &mut Buffer, days: &DaySelector, is_active: bool) {
+    let border_style = if is_active { Color::White } else { COL_BORDER };
+
+    Paragraph::new(Line::from(day_spans(days, is_active)))
+        .block(field(&capitalise(DAYS), COL_BLUE, border_style))
+        .render(area, buf);
 }
 
 // Modal
@@ -527,7 +797,7 @@ pub fn modal(area: Rect, buf: &mut Buffer, app: &mut App) {
         Some(Modal::Replace) => Constraint::Length(6),
         Some(Modal::Job) => match app.selected_job.as_ref().unwrap().frequency.as_str() {
             REAL_TIME => Constraint::Length(6),
-            _ => Constraint::Length(9),
+            _ => Constraint::Length(12),
         },
         None => Constraint::Length(9),
     };
@@ -546,22 +816,22 @@ pub fn modal(area: Rect, buf: &mut Buffer, app: &mut App) {
 
     // Render Log & LogResult table
     if let Some(log) = &app.selected_log {
-        let status_emoji = match log.status.as_str() {
-            SUCCESS => EMOJI_STATUS_SUCCESS,
-            FAILED => EMOJI_STATUS_FAILED,
-            PARTIAL => EMOJI_STATUS_PARTIAL,
-            _ => EMOJI_STATUS_OTHER,
-        };
+        let status_emoji = log_status_emoji(&log.status);
 
         let inner_vertical = Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]);
         let [first, second] = inner_vertical.areas(block.inner(area));
 
-        Paragraph::new(format!(
+        let mut header = format!(
             "⏱️ {}  |  ⏹️ {}  |  {} {}",
             log.startstamp, log.endstamp, status_emoji, log.status
-        ))
-        .alignment(Alignment::Center)
-        .render(first, buf);
+        );
+        if log.attempt > 0 {
+            header.push_str(&format!("  |  🔁 Attempt {}", log.formatted_attempt()));
+        }
+
+        Paragraph::new(header)
+            .alignment(Alignment::Center)
+            .render(first, buf);
 
         table(second, buf, LOG, app);
     }
@@ -578,6 +848,10 @@ pub fn footer(area: Rect, buf: &mut Buffer, app: &App) {
     if app.active_modal == Some(Modal::Job) {
         shortcuts.push(ACTION_CLOSE);
         shortcuts.push(ACTION_MOVE);
+        if app.active_component == Some(Component::Days) {
+            shortcuts.push(ACTION_DAYS);
+            shortcuts.push(ACTION_TOGGLE_DAY);
+        }
         shortcuts.push(ACTION_UPDATE);
     } else if let Some(comp) = &app.active_component {
         if comp.is_field() {
@@ -612,8 +886,10 @@ pub fn footer(area: Rect, buf: &mut Buffer, app: &App) {
                 shortcuts.push(ACTION_DELETE);
                 shortcuts.push(ACTION_EDIT);
                 shortcuts.push(ACTION_MIRROR);
+                shortcuts.push(ACTION_GUARD);
                 shortcuts.push(ACTION_ACTIVE);
                 shortcuts.push(ACTION_CLONE);
+                shortcuts.push(ACTION_CANCEL);
 
                 if count > 1 {
                     shortcuts.push(ACTION_ENABLE);
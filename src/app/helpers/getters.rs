@@ -13,8 +13,15 @@ impl App {
             Component::Search => Some(&mut self.search),
             Component::Source => Some(&mut self.source),
             Component::Target => Some(&mut self.target),
-            Component::Hour => Some(&mut self.hour),
-            Component::Day => Some(&mut self.day),
+            Component::Time => Some(&mut self.time),
+            Component::Retries => Some(&mut self.retries),
+            Component::Delay => Some(&mut self.delay),
+            Component::Backoff => Some(&mut self.backoff),
+            Component::Timeout => Some(&mut self.timeout),
+            Component::Trigger => Some(&mut self.trigger),
+            Component::OncePer => Some(&mut self.once_per),
+            Component::Backend => Some(&mut self.backend),
+            Component::Interval => Some(&mut self.interval),
             Component::ReplaceWith => Some(&mut self.replace_with),
             Component::ToReplace => Some(&mut self.to_replace),
             _ => None,
@@ -10,8 +10,9 @@ use super::super::app::App;
 use crate::{
     app::structs::{Component, Modal},
     consts::{
-        ACTIVATE, DEACTIVATE, SCROLL_DOWN, SCROLL_UP, SHORTCUT_DAILY, SHORTCUT_FILTER,
-        SHORTCUT_NEW, SHORTCUT_QUIT, SHORTCUT_REAL_TIME, SHORTCUT_SEARCH, SHORTCUT_WEEKLY,
+        ACTIVATE, DEACTIVATE, SCROLL_DOWN, SCROLL_UP, SHORTCUT_CANCEL, SHORTCUT_DAILY,
+        SHORTCUT_FILTER, SHORTCUT_GUARD, SHORTCUT_MOUNT, SHORTCUT_NEW, SHORTCUT_QUIT,
+        SHORTCUT_REAL_TIME, SHORTCUT_SEARCH, SHORTCUT_SORT, SHORTCUT_WEEKLY,
     },
     structs::Job,
     utils::{get_active_jobs, get_active_logs},
@@ -51,6 +52,17 @@ impl App {
                 },
                 _ => {}
             }
+        } else if self.active_component == Some(Component::Days) {
+            match (modifiers, code) {
+                (_, Left) => self.days.move_left(),
+                (_, Right) => self.days.move_right(),
+                (_, Char(' ')) => self.days.toggle(self.days.index),
+                (_, Char(c @ '1'..='7')) => self.days.toggle(c as usize - '1' as usize),
+                (_, Up) => self.handle_scroll(SCROLL_UP)?,
+                (_, Down) => self.handle_scroll(SCROLL_DOWN)?,
+                (_, Enter) => self.commit_record(),
+                _ => {}
+            }
         } else if let Some(active_table) = self.get_active_table() {
             let idx = active_table.scroll;
 
@@ -77,6 +89,11 @@ impl App {
                         self.delete_record(job);
                     }
                 }
+                (_, Char(SHORTCUT_CANCEL)) => {
+                    if let Some(job) = self.get_active_job(idx).cloned() {
+                        self.cancel_run(job);
+                    }
+                }
                 (KeyModifiers::CONTROL, Char('c')) => {
                     if let Some(job) = self.get_active_job(idx).cloned() {
                         self.clone_record(job);
@@ -104,17 +121,24 @@ impl App {
                         self.toggle_mirror();
                     }
                 }
+                (_, Char(SHORTCUT_GUARD)) => {
+                    if let Some(job) = self.get_active_job(idx).cloned() {
+                        self.set_selected_job(job);
+                        self.cycle_guard();
+                    }
+                }
                 (KeyModifiers::CONTROL, Char('r')) => {
                     self.open_replace();
                 }
                 (_, Char(SHORTCUT_FILTER)) => {
                     self.filter = self.filter.next();
                 }
+                (_, Char(SHORTCUT_SORT)) => self.next_sort(),
                 (
                     _,
                     Char(
                         c @ (SHORTCUT_SEARCH | SHORTCUT_DAILY | SHORTCUT_WEEKLY
-                        | SHORTCUT_REAL_TIME),
+                        | SHORTCUT_REAL_TIME | SHORTCUT_MOUNT),
                     ),
                 ) => {
                     self.enable_component(c);
@@ -128,6 +152,7 @@ impl App {
                 (_, Char(SHORTCUT_FILTER)) => {
                     self.filter = self.filter.next();
                 }
+                (_, Char(SHORTCUT_SORT)) => self.next_sort(),
                 (KeyModifiers::CONTROL, Char('r')) => {
                     self.open_replace();
                 }
@@ -135,7 +160,7 @@ impl App {
                     _,
                     Char(
                         c @ (SHORTCUT_SEARCH | SHORTCUT_DAILY | SHORTCUT_WEEKLY
-                        | SHORTCUT_REAL_TIME),
+                        | SHORTCUT_REAL_TIME | SHORTCUT_MOUNT),
                     ),
                 ) => {
                     self.enable_component(c);
@@ -163,6 +188,7 @@ impl App {
         match event.kind {
             MouseEventKind::Down(MouseButton::Left) => {
                 self.filter_clicked = false;
+                self.sort_clicked = false;
                 self.event = Some(event);
             }
             MouseEventKind::ScrollDown => self.handle_scroll(SCROLL_DOWN)?,
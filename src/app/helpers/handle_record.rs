@@ -10,10 +10,17 @@ use super::super::{
     structs::{Component, Modal},
 };
 use crate::{
-    consts::{DAILY, REAL_TIME, WEEK_DAYS, WEEKLY},
-    db::db::{delete, insert, mass_replace, mass_update, update},
+    consts::{
+        DAILY, MAX_RETRIES, MAX_RETRY_BACKOFF, MOUNT, PER_DAY, PER_MOUNT, POLL_INTERVAL_SECS,
+        REAL_TIME, RETRY_BACKOFF, RETRY_DELAY, WATCH_BACKENDS, WEEKLY,
+    },
+    db::{
+        db::{delete, insert, mass_replace, set_active, update},
+        query::JobQuery,
+    },
+    lock::request_cancel,
     structs::{Job, Log},
-    utils::{capitalise, get_active_jobs},
+    utils::{get_active_jobs, parse_time},
 };
 
 impl App {
@@ -40,12 +47,35 @@ impl App {
         if let Some(job) = &mut self.selected_job {
             job.source = self.source.value.clone();
             job.target = self.target.value.clone();
-            job.hour = self.hour.value.parse().unwrap();
+            if let Some((hour, minute)) = parse_time(&self.time.value) {
+                job.hour = hour;
+                job.minute = minute;
+            }
+
+            if job.frequency == WEEKLY {
+                job.days = self.days.get_days();
+            }
 
-            let day = &self.day.value;
-            if !day.is_empty() {
-                let capitalised_day = capitalise(&day);
-                job.day = Some(capitalised_day);
+            if job.frequency == MOUNT {
+                job.mount_trigger = self.trigger.value.trim().to_string();
+                job.once_per = self.once_per.value.trim().to_lowercase();
+            }
+
+            if job.frequency == REAL_TIME {
+                job.watch_backend = self.backend.value.trim().to_lowercase();
+                job.poll_interval = self
+                    .interval
+                    .value
+                    .trim()
+                    .parse()
+                    .unwrap_or(POLL_INTERVAL_SECS);
+            }
+
+            if job.frequency != REAL_TIME {
+                job.retry_max = self.retries.value.trim().parse().unwrap_or(0);
+                job.retry_delay = self.delay.value.trim().parse().unwrap_or(RETRY_DELAY);
+                job.retry_backoff = self.backoff.value.trim().parse().unwrap_or(RETRY_BACKOFF);
+                job.timeout = self.timeout.value.trim().parse().unwrap_or(0);
             }
 
             let freq = job.frequency.as_str();
@@ -91,6 +121,7 @@ impl App {
 
                     self.active_component = None;
                     self.reset_values();
+                    self.sort_jobs();
                 }
                 Err(e) => println!("{e}"), //TODO: add popup for the error
             }
@@ -102,14 +133,28 @@ impl App {
 
         self.source.value = job.source.clone();
         self.target.value = job.target.clone();
-        self.hour.value = job.hour.to_string();
-        self.day.value = job.day.clone().unwrap_or_default();
+        self.time.value = job.formatted_time();
+        self.days.set_days(&job.days);
+        self.retries.value = job.retry_max.to_string();
+        self.delay.value = job.retry_delay.to_string();
+        self.backoff.value = job.retry_backoff.to_string();
+        self.timeout.value = job.timeout.to_string();
+        self.trigger.value = job.mount_trigger.clone();
+        self.once_per.value = job.once_per.clone();
+        self.backend.value = job.watch_backend.clone();
+        self.interval.value = job.poll_interval.to_string();
 
         self.selected_job = Some(job);
 
         self.commit_record();
     }
 
+    pub fn cancel_run(&mut self, job: Job) {
+        if let Err(e) = request_cancel(job.id.unwrap()) {
+            println!("{e}"); //TODO: add popup for the error
+        }
+    }
+
     pub fn delete_record(&mut self, job: Job) {
         //TODO: popup to confirm delete
 
@@ -181,7 +226,7 @@ impl App {
         let ids_to_update: Vec<u16> = found_jobs.iter().filter_map(|j| j.id).collect();
         let ids_set: HashSet<u16> = ids_to_update.iter().cloned().collect();
 
-        match mass_update(&mut self.db, &ids_to_update, "active", active) {
+        match set_active(&mut self.db, &JobQuery::ids(&ids_to_update), active) {
             Ok(_) => {
                 if let Some(jobs) = self.jobs.get_mut(section) {
                     let mut new_active_count: u16 = 0;
@@ -235,29 +280,81 @@ impl App {
         }
     }
 
+    pub fn cycle_guard(&mut self) {
+        if let Some(job) = &mut self.selected_job {
+            job.target_guard = job.next_guard();
+
+            let freq = job.frequency.as_str();
+
+            match update(&mut self.db, job) {
+                Ok(_) => {
+                    let jobs = self.jobs.get_mut(freq).unwrap();
+
+                    if let Some(iter_job) = jobs
+                        .iter_mut()
+                        .find(|iter_job| iter_job.id == Some(job.id.unwrap()))
+                    {
+                        *iter_job = job.clone();
+                    }
+                }
+                Err(e) => println!("{e}"), //TODO: add popup for the error
+            }
+
+            self.selected_job = None;
+        }
+    }
+
     fn is_record_valid(&self) -> bool {
         let source = self.source.value.as_str();
         let target = self.target.value.as_str();
-        let hour = self.hour.value.as_str();
-        let day = self.day.value.to_lowercase();
+        let time = parse_time(&self.time.value);
 
         // Check if essential fields are empty or the same
         if source.is_empty() || target.is_empty() || source == target {
             return false;
         }
 
-        match self.selected_job.as_ref().unwrap().frequency.as_str() {
-            REAL_TIME => true,
-            DAILY => self.is_hour_valid(hour),
-            WEEKLY => self.is_hour_valid(hour) && WEEK_DAYS.contains(&day.as_str()),
-            _ => false,
-        }
-    }
+        // Retries are optional, but a delay under a minute or a shrinking backoff makes no sense
+        let is_retry_valid = self
+            .retries
+            .value
+            .trim()
+            .parse::<u8>()
+            .is_ok_and(|retries| retries <= MAX_RETRIES)
+            && self
+                .delay
+                .value
+                .trim()
+                .parse::<u16>()
+                .is_ok_and(|delay| delay >= 1)
+            && self
+                .backoff
+                .value
+                .trim()
+                .parse::<f64>()
+                .is_ok_and(|backoff| (1.0..=MAX_RETRY_BACKOFF).contains(&backoff))
+            && self.timeout.value.trim().parse::<u16>().is_ok();
 
-    // Parse and validate hour (0-23)
-    fn is_hour_valid(&self, hour: &str) -> bool {
-        match hour.parse::<u8>() {
-            Ok(h) if h <= 23 => true,
+        match self.selected_job.as_ref().unwrap().frequency.as_str() {
+            // The interval only matters when polling, but is kept for a later switch
+            REAL_TIME => {
+                let backend = self.backend.value.trim().to_lowercase();
+                WATCH_BACKENDS.contains(&backend.as_str())
+                    && self
+                        .interval
+                        .value
+                        .trim()
+                        .parse::<u16>()
+                        .is_ok_and(|interval| interval >= 1)
+            }
+            DAILY => time.is_some() && is_retry_valid,
+            WEEKLY => time.is_some() && !self.days.get_days().is_empty() && is_retry_valid,
+            MOUNT => {
+                let once_per = self.once_per.value.trim().to_lowercase();
+                !self.trigger.value.trim().is_empty()
+                    && [PER_MOUNT, PER_DAY].contains(&once_per.as_str())
+                    && is_retry_valid
+            }
             _ => false,
         }
     }
@@ -273,17 +370,30 @@ impl App {
     pub fn set_selected_job(&mut self, job: Job) {
         self.selected_job = Some(job.clone());
 
+        self.time.value = job.formatted_time();
+        self.time.index = self.time.value.len();
+
+        self.days.set_days(&job.days);
+
+        for (field, value) in [
+            (&mut self.retries, job.retry_max.to_string()),
+            (&mut self.delay, job.retry_delay.to_string()),
+            (&mut self.backoff, job.retry_backoff.to_string()),
+            (&mut self.timeout, job.timeout.to_string()),
+            (&mut self.trigger, job.mount_trigger.clone()),
+            (&mut self.once_per, job.once_per.clone()),
+            (&mut self.backend, job.watch_backend.clone()),
+            (&mut self.interval, job.poll_interval.to_string()),
+        ] {
+            field.index = value.len();
+            field.value = value;
+        }
+
         self.source.value = job.source;
         self.source.index = self.source.value.len();
 
         self.target.value = job.target;
         self.target.index = self.target.value.len();
-
-        self.hour.value = job.hour.to_string();
-        self.hour.index = self.hour.value.len();
-
-        self.day.value = job.day.unwrap_or_default();
-        self.day.index = self.day.value.len();
     }
 
     pub fn replace_string(&mut self) {
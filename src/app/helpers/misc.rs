@@ -1,14 +1,25 @@
 // Standards ─────────────────────────────────────────────────────
-use std::io::stdout;
+use std::{
+    cmp::Reverse,
+    io::stdout,
+    time::{Duration, Instant},
+};
 
 // Crates ────────────────────────────────────────────────────────
+use chrono::Local;
 use crossterm::{event::DisableMouseCapture, execute};
 
 // mods ──────────────────────────────────────────────────────────
 use super::super::app::App;
 use crate::{
-    app::structs::{Component, Modal},
-    consts::{SHORTCUT_DAILY, SHORTCUT_REAL_TIME, SHORTCUT_SEARCH, SHORTCUT_WEEKLY},
+    app::structs::{Component, Modal, Sort},
+    consts::{
+        DOWN, REAL_TIME, SHORTCUT_DAILY, SHORTCUT_MOUNT, SHORTCUT_REAL_TIME, SHORTCUT_SEARCH,
+        SHORTCUT_WEEKLY, SUCCESS, WATCHER_REFRESH_MS,
+    },
+    control::watch_statuses,
+    db::db::{get_heartbeat, get_watch_states},
+    utils::watcher_health,
 };
 
 impl App {
@@ -40,6 +51,7 @@ impl App {
         if is_search || is_table {
             self.event = None;
             self.filter_clicked = false;
+            self.sort_clicked = false;
             self.active_component = match self.show_journal {
                 true => Some(Component::Journal),
                 false => None,
@@ -59,6 +71,7 @@ impl App {
 
             self.event = None;
             self.filter_clicked = false;
+            self.sort_clicked = false;
             self.active_modal = None;
             self.selected_job = None;
 
@@ -66,15 +79,84 @@ impl App {
                 &mut self.search,
                 &mut self.source,
                 &mut self.target,
-                &mut self.hour,
-                &mut self.day,
+                &mut self.time,
+                &mut self.retries,
+                &mut self.delay,
+                &mut self.backoff,
+                &mut self.timeout,
+                &mut self.trigger,
+                &mut self.once_per,
+                &mut self.backend,
+                &mut self.interval,
             ] {
                 field.value.clear();
                 field.index = 0;
             }
+            self.days.clear();
+        }
+    }
+
+    // Failed runs first when sorting by status, jobs that never ran last
+    pub fn sort_jobs(&mut self) {
+        let now = Local::now().naive_local();
+
+        for jobs in self.jobs.values_mut() {
+            jobs.sort_by_key(|job| job.id);
+
+            match self.sort {
+                Sort::Id => {}
+                Sort::LastRun => jobs.sort_by_key(|job| Reverse(job.last_run)),
+                Sort::NextRun => jobs.sort_by_key(|job| {
+                    let next_run = job.next_run(&now);
+                    (next_run.is_none(), next_run)
+                }),
+                Sort::LastStatus => jobs.sort_by_key(|job| match job.last_status.as_deref() {
+                    Some(SUCCESS) => 2,
+                    Some(_) => 1,
+                    None => 3,
+                }),
+            }
+        }
+    }
+
+    // Asks syncrab_w & its heartbeat at most every WATCHER_REFRESH_MS.
+    // Jobs it does not watch get no status, and none at all while it is down
+    pub fn refresh_watcher(&mut self) {
+        let refresh = Duration::from_millis(WATCHER_REFRESH_MS);
+        if self
+            .watcher_refreshed_at
+            .is_some_and(|refreshed_at| refreshed_at.elapsed() < refresh)
+        {
+            return;
+        }
+        self.watcher_refreshed_at = Some(Instant::now());
+
+        self.heartbeat = get_heartbeat(&self.db);
+        let is_down = watcher_health(self.heartbeat.as_ref(), &Local::now().naive_local()) == DOWN;
+
+        let (statuses, states) = if is_down {
+            Default::default()
+        } else {
+            (
+                watch_statuses().unwrap_or_default(),
+                get_watch_states(&self.db),
+            )
+        };
+
+        if let Some(jobs) = self.jobs.get_mut(REAL_TIME) {
+            for job in jobs {
+                let id = job.id.unwrap();
+                job.watch_status = statuses.get(&id).cloned();
+                job.watch_state = states.get(&id).map(|state| state.state.to_string());
+            }
         }
     }
 
+    pub fn next_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.sort_jobs();
+    }
+
     pub fn enable_component(&mut self, c: char) {
         self.event = None;
         self.active_component = match c {
@@ -82,6 +164,7 @@ impl App {
             SHORTCUT_REAL_TIME => Some(Component::RealTime),
             SHORTCUT_DAILY => Some(Component::Daily),
             SHORTCUT_WEEKLY => Some(Component::Weekly),
+            SHORTCUT_MOUNT => Some(Component::Mount),
             _ => None,
         };
     }
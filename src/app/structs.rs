@@ -9,8 +9,9 @@ use std::{
 
 // mods ─────────────────────────────────────────────────────────
 use crate::consts::{
-    ACTIVE, ALL, DAILY, DAY, DELIMITERS, HOUR, INACTIVE, JOURNAL, LOG, REAL_TIME, REPLACE_WITH,
-    SEARCH, SOURCE, TARGET, TO_REPLACE, WEEKLY,
+    ACTIVE, ALL, BACKEND, BACKOFF, DAILY, DAYS, DELAY, DELIMITERS, ID, INACTIVE, INTERVAL, JOURNAL,
+    LAST_RUN, LAST_STATUS, LOG, MOUNT, NEXT_RUN, ONCE_PER, REAL_TIME, REPLACE_WITH, RETRIES,
+    SEARCH, SOURCE, TARGET, TIME, TIMEOUT, TO_REPLACE, TRIGGER, WEEK_DAYS, WEEKLY,
 };
 
 // Crates ───────────────────────────────────────────────────────
@@ -41,6 +42,14 @@ impl Filter {
             Filter::Inactive => Filter::All,
         }
     }
+
+    pub fn active(&self) -> Option<bool> {
+        match self {
+            Filter::All => None,
+            Filter::Active => Some(true),
+            Filter::Inactive => Some(false),
+        }
+    }
 }
 
 impl fmt::Display for Filter {
@@ -69,6 +78,38 @@ impl FromStr for Filter {
     }
 }
 
+// Sort
+#[derive(Debug, Default, PartialEq)]
+pub enum Sort {
+    #[default]
+    Id,
+    LastRun,
+    NextRun,
+    LastStatus,
+}
+
+impl Sort {
+    pub fn next(&self) -> Self {
+        match self {
+            Sort::Id => Sort::LastRun,
+            Sort::LastRun => Sort::NextRun,
+            Sort::NextRun => Sort::LastStatus,
+            Sort::LastStatus => Sort::Id,
+        }
+    }
+}
+
+impl fmt::Display for Sort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Sort::Id => write!(f, "{}", ID),
+            Sort::LastRun => write!(f, "{}", LAST_RUN),
+            Sort::NextRun => write!(f, "{}", NEXT_RUN),
+            Sort::LastStatus => write!(f, "{}", LAST_STATUS),
+        }
+    }
+}
+
 // Component
 #[derive(Debug, PartialEq, Clone)]
 pub enum Component {
@@ -77,11 +118,20 @@ pub enum Component {
     Log,
     Source,
     Target,
-    Hour,
-    Day,
+    Time,
+    Days,
+    Retries,
+    Delay,
+    Backoff,
+    Timeout,
+    Trigger,
+    OncePer,
+    Backend,
+    Interval,
     Daily,
     Weekly,
     RealTime,
+    Mount,
     ToReplace,
     ReplaceWith,
 }
@@ -91,11 +141,20 @@ impl Component {
         match s {
             SOURCE => Component::Source,
             TARGET => Component::Target,
-            HOUR => Component::Hour,
-            DAY => Component::Day,
+            TIME => Component::Time,
+            DAYS => Component::Days,
+            RETRIES => Component::Retries,
+            DELAY => Component::Delay,
+            BACKOFF => Component::Backoff,
+            TIMEOUT => Component::Timeout,
+            TRIGGER => Component::Trigger,
+            ONCE_PER => Component::OncePer,
+            BACKEND => Component::Backend,
+            INTERVAL => Component::Interval,
             DAILY => Component::Daily,
             WEEKLY => Component::Weekly,
             REAL_TIME => Component::RealTime,
+            MOUNT => Component::Mount,
             TO_REPLACE => Component::ToReplace,
             REPLACE_WITH => Component::ReplaceWith,
             _ => panic!("❌ Could not parse the value [{}] to the enum Component", s),
@@ -109,11 +168,20 @@ impl Component {
             Component::Log => LOG,
             Component::Source => SOURCE,
             Component::Target => TARGET,
-            Component::Hour => HOUR,
-            Component::Day => DAY,
+            Component::Time => TIME,
+            Component::Days => DAYS,
+            Component::Retries => RETRIES,
+            Component::Delay => DELAY,
+            Component::Backoff => BACKOFF,
+            Component::Timeout => TIMEOUT,
+            Component::Trigger => TRIGGER,
+            Component::OncePer => ONCE_PER,
+            Component::Backend => BACKEND,
+            Component::Interval => INTERVAL,
             Component::Daily => DAILY,
             Component::Weekly => WEEKLY,
             Component::RealTime => REAL_TIME,
+            Component::Mount => MOUNT,
             Component::ToReplace => TO_REPLACE,
             Component::ReplaceWith => REPLACE_WITH,
         }
@@ -125,8 +193,16 @@ impl Component {
             Component::Search
                 | Component::Source
                 | Component::Target
-                | Component::Hour
-                | Component::Day
+                | Component::Time
+                | Component::Days
+                | Component::Retries
+                | Component::Delay
+                | Component::Backoff
+                | Component::Timeout
+                | Component::Trigger
+                | Component::OncePer
+                | Component::Backend
+                | Component::Interval
                 | Component::ToReplace
                 | Component::ReplaceWith
         )
@@ -138,23 +214,36 @@ impl Component {
             Component::Daily
                 | Component::Weekly
                 | Component::RealTime
+                | Component::Mount
                 | Component::Journal
                 | Component::Log
         )
     }
 
     pub fn is_listable(&self) -> bool {
-        matches!(&self, Component::Source | Component::Target)
+        matches!(
+            &self,
+            Component::Source | Component::Target | Component::Trigger
+        )
     }
 
     pub fn next(self, freq: Option<Component>) -> Self {
         match (freq, &self) {
             (Some(_), Component::Source) => Component::Target,
-            (Some(Component::RealTime), Component::Target) => Component::Source,
-            (Some(Component::Daily | Component::Weekly), Component::Target) => Component::Hour,
-            (Some(Component::Daily), Component::Hour) => Component::Source,
-            (Some(Component::Weekly), Component::Hour) => Component::Day,
-            (Some(Component::Weekly), Component::Day) => Component::Source,
+            (Some(Component::RealTime), Component::Target) => Component::Backend,
+            (Some(_), Component::Backend) => Component::Interval,
+            (Some(_), Component::Interval) => Component::Source,
+            (Some(Component::Daily | Component::Weekly), Component::Target) => Component::Time,
+            (Some(Component::Mount), Component::Target) => Component::Trigger,
+            (Some(Component::Daily), Component::Time) => Component::Retries,
+            (Some(Component::Weekly), Component::Time) => Component::Days,
+            (Some(Component::Weekly), Component::Days) => Component::Retries,
+            (Some(_), Component::Trigger) => Component::OncePer,
+            (Some(_), Component::OncePer) => Component::Retries,
+            (Some(_), Component::Retries) => Component::Delay,
+            (Some(_), Component::Delay) => Component::Backoff,
+            (Some(_), Component::Backoff) => Component::Timeout,
+            (Some(_), Component::Timeout) => Component::Source,
             (None, Component::ReplaceWith) => Component::ToReplace,
             (None, Component::ToReplace) => Component::ReplaceWith,
             _ => self,
@@ -164,11 +253,22 @@ impl Component {
     pub fn previous(self, freq: Option<Component>) -> Self {
         match (freq, &self) {
             (Some(_), Component::Target) => Component::Source,
-            (Some(_), Component::Hour) => Component::Target,
-            (Some(Component::RealTime), Component::Source) => Component::Target,
-            (Some(Component::Daily), Component::Source) => Component::Hour,
-            (Some(Component::Weekly), Component::Day) => Component::Hour,
-            (Some(Component::Weekly), Component::Source) => Component::Day,
+            (Some(_), Component::Time) => Component::Target,
+            (Some(Component::RealTime), Component::Source) => Component::Interval,
+            (Some(_), Component::Interval) => Component::Backend,
+            (Some(_), Component::Backend) => Component::Target,
+            (Some(Component::Daily | Component::Weekly | Component::Mount), Component::Source) => {
+                Component::Timeout
+            }
+            (Some(_), Component::Timeout) => Component::Backoff,
+            (Some(Component::Weekly), Component::Days) => Component::Time,
+            (Some(Component::Daily), Component::Retries) => Component::Time,
+            (Some(Component::Weekly), Component::Retries) => Component::Days,
+            (Some(Component::Mount), Component::Retries) => Component::OncePer,
+            (Some(_), Component::OncePer) => Component::Trigger,
+            (Some(_), Component::Trigger) => Component::Target,
+            (Some(_), Component::Delay) => Component::Retries,
+            (Some(_), Component::Backoff) => Component::Delay,
             (None, Component::ReplaceWith) => Component::ToReplace,
             (None, Component::ToReplace) => Component::ReplaceWith,
             _ => self,
@@ -184,11 +284,20 @@ impl fmt::Display for Component {
             Component::Log => write!(f, "{}", LOG),
             Component::Source => write!(f, "{}", SOURCE),
             Component::Target => write!(f, "{}", TARGET),
-            Component::Hour => write!(f, "{}", HOUR),
-            Component::Day => write!(f, "{}", DAY),
+            Component::Time => write!(f, "{}", TIME),
+            Component::Days => write!(f, "{}", DAYS),
+            Component::Retries => write!(f, "{}", RETRIES),
+            Component::Delay => write!(f, "{}", DELAY),
+            Component::Backoff => write!(f, "{}", BACKOFF),
+            Component::Timeout => write!(f, "{}", TIMEOUT),
+            Component::Trigger => write!(f, "{}", TRIGGER),
+            Component::OncePer => write!(f, "{}", ONCE_PER),
+            Component::Backend => write!(f, "{}", BACKEND),
+            Component::Interval => write!(f, "{}", INTERVAL),
             Component::Daily => write!(f, "{}", DAILY),
             Component::Weekly => write!(f, "{}", WEEKLY),
             Component::RealTime => write!(f, "{}", REAL_TIME),
+            Component::Mount => write!(f, "{}", MOUNT),
 
             Component::ToReplace => write!(f, "{}", TO_REPLACE),
             Component::ReplaceWith => write!(f, "{}", REPLACE_WITH),
@@ -434,3 +543,48 @@ pub struct SuggestionState {
     pub len: usize,
     pub active: bool,
 }
+
+// DaySelector
+#[derive(Debug, Default)]
+pub struct DaySelector {
+    pub selected: [bool; 7],
+    pub index: usize,
+}
+
+impl DaySelector {
+    pub fn set_days(&mut self, days: &[String]) {
+        for (i, week_day) in WEEK_DAYS.iter().enumerate() {
+            self.selected[i] = days.iter().any(|day| day == week_day);
+        }
+        self.index = 0;
+    }
+
+    pub fn get_days(&self) -> Vec<String> {
+        WEEK_DAYS
+            .iter()
+            .zip(self.selected)
+            .filter(|(_, selected)| *selected)
+            .map(|(day, _)| day.to_string())
+            .collect()
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        if let Some(selected) = self.selected.get_mut(index) {
+            *selected = !*selected;
+            self.index = index;
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        self.index = self.index.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        self.index = (self.index + 1).min(WEEK_DAYS.len() - 1);
+    }
+
+    pub fn clear(&mut self) {
+        self.selected = [false; 7];
+        self.index = 0;
+    }
+}
@@ -1,30 +1,56 @@
 // Standards ─────────────────────────────────────────────────────
-use std::{collections::HashMap, env, process};
+use std::{collections::HashMap, env, process, sync::Arc, time::Duration};
 
 // Crates ───────────────────────────────────────────────────────
-use chrono::{DateTime, Datelike, Local, Timelike};
+use chrono::{DateTime, Local};
+use rusqlite::Connection;
 
 // mods ──────────────────────────────────────────────────────────
 use syncrab::{
-    consts::{ACTIVE, ALL, DAILY, INACTIVE, REAL_TIME, VALID_OPTS_1, VALID_OPTS_2, WEEKLY},
-    db::db::{get_jobs_to_run, init_db},
+    consts::{
+        ACTIVE, ALL, DAILY, DEFERRED, DUE_FORMAT, INACTIVE, LOCK_WAIT_SECS, MOUNT, REAL_TIME,
+        RETRY, VALID_OPTS_1, VALID_OPTS_2, WEEKLY,
+    },
+    db::{
+        db::{get_job, get_jobs_to_run, init_db, take_due_runs},
+        query::JobQuery,
+    },
+    lock::{CancelToken, Interrupt, JobLock, LockError, run_interruptible},
+    retention::prune_if_due,
     structs::{Job, Log, LogResult},
-    utils::{are_paths_valid, copy_dir, count_children, log_results, normalise_path},
+    utils::{
+        are_paths_valid, copy_dir, count_children, defer_run, log_results, normalise_path,
+        schedule_retries,
+    },
 };
 
 // Init ──────────────────────────────────────────────────────────
 fn main() {
     let now: DateTime<Local> = Local::now();
-    let log = Log::new();
+    let mut log = Log::new();
 
     let args = prompt_user();
+    let is_scheduled_run = args.is_none();
 
     let mut conn = init_db();
-    let jobs: HashMap<&'static str, Vec<Job>> =
-        get_jobs_to_run(&conn, args, now.weekday().to_string(), now.hour() as u8);
+    let (jobs, blocked): (HashMap<&'static str, Vec<Job>>, _) =
+        get_jobs_to_run(&conn, args, &now.naive_local());
+
+    // Blocked by a blackout window or a pause: nothing runs, the jobs due now run once it lifts
+    if let Some(until) = blocked {
+        println!(
+            "⏸️ Scheduled backups are blocked until {}",
+            until.format(DUE_FORMAT)
+        );
+        for job in jobs.values().flatten() {
+            defer_run(&mut conn, job.id.unwrap(), &until);
+        }
+        return;
+    }
 
     let mut success_directories: Vec<LogResult> = Vec::new();
     let mut failed_directories: Vec<LogResult> = Vec::new();
+    let mut interrupts: Vec<(u16, Interrupt)> = Vec::new();
 
     for (freq, jobs) in jobs.iter() {
         if jobs.is_empty() {
@@ -35,80 +61,267 @@ fn main() {
         println!("──────────────────────────────────────────────────────────\n");
 
         let jobs_count = jobs.len();
-        let jobs_width = jobs_count.to_string().len();
 
         for (i, job) in jobs.iter().enumerate() {
-            let frequency = freq.to_string();
-            let source = normalise_path(&job.source);
-            let target = normalise_path(&job.target);
+            if let Some(interrupt) = run_job(
+                freq,
+                job,
+                (i + 1, jobs_count),
+                false,
+                &mut success_directories,
+                &mut failed_directories,
+            ) {
+                interrupts.push((job.id.unwrap(), interrupt));
+            }
+        }
+
+        println!("\n──────────────────────────────────────────────────────────");
+        println!("✅ Backups completed successfully!");
+    }
+
+    let failed_ids = retryable_job_ids(&failed_directories, &interrupts);
+    if let Some(status) =
+        interrupted_status(&interrupts, &success_directories, &failed_directories)
+    {
+        log.status = status.into();
+    }
+    let log_id = log_results(&mut conn, log, success_directories, failed_directories);
+    schedule_retries(&mut conn, &failed_ids, log_id, 0);
+
+    // Retries and deferred runs only run on the scheduled (argument-less) invocation
+    if is_scheduled_run {
+        run_due_runs(&mut conn, &now);
+    }
+
+    prune_if_due(&mut conn, &now.naive_local());
+}
+
+fn run_due_runs(conn: &mut Connection, now: &DateTime<Local>) {
+    let due_runs = match take_due_runs(conn, &now.format(DUE_FORMAT).to_string()) {
+        Ok(due_runs) => due_runs,
+        Err(error) => {
+            eprintln!("{}", error);
+            return;
+        }
+    };
+
+    for pending_run in due_runs.iter() {
+        let Some(job) = get_job(conn, pending_run.job_id) else {
+            continue;
+        };
+
+        if job.active == 0 {
+            continue;
+        }
 
+        let is_retry = pending_run.reason == RETRY;
+        if is_retry {
             println!(
-                "[{}/{}] - Copying [{}] 👉 [{}]",
-                format!("{:0width$}", i + 1, width = jobs_width),
-                jobs_count,
-                source.display(),
-                target.display()
+                "\n🔁 Retrying job [{}] (attempt {} of {})...",
+                pending_run.job_id, pending_run.attempt, job.retry_max
             );
+        } else {
+            println!("\n⏯️ Running deferred job [{}]...", pending_run.job_id);
+        }
+        println!("──────────────────────────────────────────────────────────\n");
 
-            if !are_paths_valid(&frequency, job, &source, &target, &mut failed_directories) {
-                continue;
-            }
+        let mut log = Log::new_retry(pending_run);
+        let mut success_directories: Vec<LogResult> = Vec::new();
+        let mut failed_directories: Vec<LogResult> = Vec::new();
+
+        // A real-time job deferred by the watcher stands in for its sync, so it keeps its layout
+        let into_target = pending_run.reason == DEFERRED && job.frequency == REAL_TIME;
+
+        let interrupts: Vec<(u16, Interrupt)> = run_job(
+            &job.frequency,
+            &job,
+            (1, 1),
+            into_target,
+            &mut success_directories,
+            &mut failed_directories,
+        )
+        .map(|interrupt| (pending_run.job_id, interrupt))
+        .into_iter()
+        .collect();
+
+        let failed_ids = retryable_job_ids(&failed_directories, &interrupts);
+        if let Some(status) =
+            interrupted_status(&interrupts, &success_directories, &failed_directories)
+        {
+            log.status = status.into();
+        }
+        let log_id = log_results(conn, log, success_directories, failed_directories);
+
+        // A deferred run is the job's real run, so its retries hang off it
+        if is_retry {
+            schedule_retries(
+                conn,
+                &failed_ids,
+                pending_run.origin_log_id,
+                pending_run.attempt,
+            );
+        } else {
+            schedule_retries(conn, &failed_ids, log_id, 0);
+        }
+    }
+}
+
+// `position`: (current, total) for the progress prefix.
+// `into_target`: copy the source's contents straight into the target, as syncrab_w does,
+// instead of into a folder named after the source.
+// Returns what stopped the copy when it was cancelled or timed out
+fn run_job(
+    freq: &str,
+    job: &Job,
+    position: (usize, usize),
+    into_target: bool,
+    success_directories: &mut Vec<LogResult>,
+    failed_directories: &mut Vec<LogResult>,
+) -> Option<Interrupt> {
+    let (current, jobs_count) = position;
+    let jobs_width = jobs_count.to_string().len();
 
-            let dest_path = match source.file_name() {
-                Some(name) => target.join(name),
-                None => {
-                    failed_directories.push(LogResult::new(
-                        &frequency,
-                        &format!("Source path [{}] has no file name", source.display()),
-                        &job.source,
-                        &job.target,
-                    ));
-                    continue;
-                }
-            };
-
-            let children_count = count_children(&source);
-
-            match copy_dir(&source, &dest_path, job.mirror, children_count, &mut 0) {
-                Ok(_) => success_directories.push(LogResult::new(
-                    &frequency,
-                    "OK",
-                    &job.source,
-                    &job.target,
-                )),
-                Err(error) => failed_directories.push(LogResult::new(
-                    &frequency,
-                    &error,
-                    &job.source,
-                    &job.target,
-                )),
-            };
-
-            if children_count > 1 {
-                println!("");
+    let frequency = freq.to_string();
+    let source = normalise_path(&job.source);
+    let target = normalise_path(&job.target);
+
+    println!(
+        "[{}/{}] - Copying [{}] 👉 [{}]",
+        format!("{:0width$}", current, width = jobs_width),
+        jobs_count,
+        source.display(),
+        target.display()
+    );
+
+    if !are_paths_valid(&frequency, job, &source, &target, failed_directories) {
+        return None;
+    }
+
+    // Held until the end of this run, so the watcher or another run can't sync it meanwhile
+    let lock = match JobLock::acquire(job.id.unwrap(), Some(Duration::from_secs(LOCK_WAIT_SECS))) {
+        Ok(lock) => {
+            if let Some(holder) = &lock.stale_holder {
+                println!("🧹 Recovered a stale lock left by [{}]", holder);
             }
+            lock
         }
+        Err(LockError::Busy(reason)) => {
+            println!("⏭️ Skipped because {}", reason);
+            return None;
+        }
+        Err(LockError::Failed(error)) => {
+            failed_directories.push(LogResult::new(&frequency, &error, job));
+            return None;
+        }
+    };
 
-        println!("\n──────────────────────────────────────────────────────────");
-        println!("✅ Backups completed successfully!");
+    let cancel = Arc::new(CancelToken::new(
+        job.id.unwrap(),
+        lock,
+        job.timeout_duration(),
+    ));
+
+    let dest_path = match source.file_name() {
+        _ if into_target => target.clone(),
+        Some(name) => target.join(name),
+        None => {
+            failed_directories.push(LogResult::new(
+                &frequency,
+                &format!("Source path [{}] has no file name", source.display()),
+                job,
+            ));
+            return None;
+        }
+    };
+
+    // On its own thread, so a copy hanging on a dead mount can't outlive the job's timeout
+    let mirror = job.mirror;
+    let (children_count, result) = match run_interruptible(&cancel, move |cancel| {
+        let children_count = count_children(&source);
+        let result = copy_dir(
+            &source,
+            &dest_path,
+            mirror,
+            children_count,
+            &mut 0,
+            cancel,
+            // Standing in for the watcher, it deletes like the watcher does
+            into_target.then_some(target.as_path()),
+        );
+        (children_count, result)
+    }) {
+        Ok(outcome) => outcome,
+        Err(error) => (0, Err(error)),
+    };
+
+    match result {
+        Ok(_) => success_directories.push(LogResult::new(&frequency, "OK", job)),
+        Err(error) => failed_directories.push(LogResult::new(&frequency, &error, job)),
+    };
+
+    if children_count > 1 {
+        println!("");
+    }
+
+    if let Some(interrupt) = cancel.interrupt() {
+        println!("⛔ Stopped because the run {}", interrupt.to_str());
+    }
+
+    cancel.interrupt()
+}
+
+// Cancelled jobs were stopped on purpose, so they are not retried
+fn retryable_job_ids(
+    failed_directories: &[LogResult],
+    interrupts: &[(u16, Interrupt)],
+) -> Vec<u16> {
+    let mut ids: Vec<u16> = failed_directories
+        .iter()
+        .filter_map(|result| result.job_id)
+        .filter(|id| !interrupts.contains(&(*id, Interrupt::Cancelled)))
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+// The log only takes an interruption's status when it stopped every job the log holds,
+// otherwise each interrupted job's result says so. A cancellation outranks a timeout
+fn interrupted_status(
+    interrupts: &[(u16, Interrupt)],
+    success_directories: &[LogResult],
+    failed_directories: &[LogResult],
+) -> Option<&'static str> {
+    let all_interrupted = success_directories.is_empty()
+        && failed_directories.iter().all(|result| {
+            interrupts
+                .iter()
+                .any(|(job_id, _)| result.job_id == Some(*job_id))
+        });
+    if !all_interrupted {
+        return None;
     }
 
-    log_results(&mut conn, log, success_directories, failed_directories);
+    interrupts
+        .iter()
+        .map(|(_, interrupt)| *interrupt)
+        .max_by_key(|interrupt| *interrupt == Interrupt::Cancelled)
+        .map(|interrupt| interrupt.to_str())
 }
 
-fn prompt_user() -> Option<(String, Option<String>)> {
+fn prompt_user() -> Option<JobQuery> {
     let args: Vec<String> = env::args().skip(1).collect();
 
     match args.len() {
         0 => None,
         1 => {
             let arg = args[0].to_lowercase();
-            if [ALL, REAL_TIME, DAILY, WEEKLY].contains(&arg.as_str()) {
-                Some((arg, None))
+            if VALID_OPTS_1.contains(&arg.as_str()) {
+                Some(JobQuery::from_args(&arg, None))
             } else {
                 eprintln!(
-                    "❌ Invalid argument: '{}'. Must be one of: {}, {}, {}, {}",
-                    arg, ALL, REAL_TIME, DAILY, WEEKLY
+                    "❌ Invalid argument: '{}'. Must be one of: {}, {}, {}, {}, {}",
+                    arg, ALL, REAL_TIME, DAILY, WEEKLY, MOUNT
                 );
                 process::exit(1);
             }
@@ -118,19 +331,19 @@ fn prompt_user() -> Option<(String, Option<String>)> {
             let arg2 = args[1].to_lowercase();
 
             if VALID_OPTS_1.contains(&arg1.as_str()) && VALID_OPTS_2.contains(&arg2.as_str()) {
-                Some((arg1, Some(arg2))) // Two valid arguments
+                Some(JobQuery::from_args(&arg1, Some(&arg2))) // Two valid arguments
             } else {
                 eprintln!(
-                    "❌ Invalid arguments: '{} {}'. Usage: syncrab_b [Optional: {}, {}, {} | {}] [Optional: {}, {}]",
-                    arg1, arg2, ALL, REAL_TIME, DAILY, WEEKLY, ACTIVE, INACTIVE
+                    "❌ Invalid arguments: '{} {}'. Usage: syncrab_b [Optional: {}, {}, {}, {} | {}] [Optional: {}, {}]",
+                    arg1, arg2, ALL, REAL_TIME, DAILY, WEEKLY, MOUNT, ACTIVE, INACTIVE
                 );
                 process::exit(1);
             }
         }
         _ => {
             eprintln!(
-                "❌ Too many arguments. Usage: syncrab_b [Optional: {}, {}, {} | {}] [Optional: {}, {}]",
-                ALL, REAL_TIME, DAILY, WEEKLY, ACTIVE, INACTIVE
+                "❌ Too many arguments. Usage: syncrab_b [Optional: {}, {}, {}, {} | {}] [Optional: {}, {}]",
+                ALL, REAL_TIME, DAILY, WEEKLY, MOUNT, ACTIVE, INACTIVE
             );
             process::exit(1);
         }
@@ -7,8 +7,12 @@ pub const SHORTCUT_SEARCH: char = 's'; // customisable
 pub const SHORTCUT_REAL_TIME: char = 'r'; // customisable
 pub const SHORTCUT_DAILY: char = 'd'; // customisable
 pub const SHORTCUT_WEEKLY: char = 'w'; // customisable
+pub const SHORTCUT_MOUNT: char = 'o'; // customisable
+pub const SHORTCUT_SORT: char = 't'; // customisable
 pub const SHORTCUT_QUIT: char = 'q'; // customisable
 pub const SHORTCUT_NEW: char = 'n'; // customisable
+pub const SHORTCUT_CANCEL: char = 'x'; // customisable
+pub const SHORTCUT_GUARD: char = 'g'; // customisable
 
 // table style ────────────────────────────────────────────────
 pub const ARROW_UP: &str = "⏶"; // customisable
@@ -18,11 +22,116 @@ pub const SLIDER: &str = "│"; // customisable
 
 // cron ───────────────────────────────────────────────────────
 pub const LOG_PATH: &str = "$HOME/syncrab.log"; // customisable
-pub const VALID_OPTS_1: [&str; 4] = [ALL, DAILY, WEEKLY, REAL_TIME];
+pub const VALID_OPTS_1: [&str; 5] = [ALL, DAILY, WEEKLY, REAL_TIME, MOUNT];
 pub const VALID_OPTS_2: [&str; 2] = [ACTIVE, INACTIVE];
 
+// installer ──────────────────────────────────────────────────
+pub const BACKUP_BIN: &str = "syncrab_b";
+pub const WATCHER_BIN: &str = "syncrab_w";
+pub const WATCHER_SERVICE: &str = "syncrab_w.service";
+pub const BACKUP_SERVICE: &str = "syncrab_b.service";
+pub const BACKUP_TIMER: &str = "syncrab_b.timer";
+pub const CRON_MARKER: &str = "# syncrab";
+pub const CRON: &str = "cron";
+pub const SYSTEMD: &str = "systemd";
+pub const INSTALL: &str = "install";
+pub const UNINSTALL: &str = "uninstall";
+pub const STATUS: &str = "status";
+pub const DRY_RUN: &str = "--dry-run";
+pub const CANCEL: &str = "cancel";
+pub const PAUSE: &str = "pause";
+pub const RESUME: &str = "resume";
+pub const BLACKOUT: &str = "blackout";
+pub const PRUNE: &str = "prune";
+pub const ADD: &str = "add";
+pub const REMOVE: &str = "remove";
+
+// watcher ────────────────────────────────────────────────────
+pub const DEBOUNCE_MS: u64 = 500; // customisable, how long a job's events must settle before syncing
+pub const DEBOUNCE_MAX_MS: u64 = 5000; // customisable, longest a busy job waits before syncing anyway
+pub const RESCAN_INTERVAL_SECS: u64 = 3600; // customisable, how often real-time jobs are fully reconciled anyway
+pub const WATCH_WORKERS: usize = 4; // customisable, how many real-time syncs may run side by side
+pub const MAX_USER_WATCHES: &str = "/proc/sys/fs/inotify/max_user_watches";
+pub const MAX_USER_INSTANCES: &str = "/proc/sys/fs/inotify/max_user_instances";
+pub const DEBUG_VAR: &str = "SYNCRAB_DEBUG"; // set it (to anything) for syncrab_w to log the events it ignores
+pub const USE_TRASH: bool = false; // customisable, move files deleted by mirrored real-time jobs into TRASH_DIR
+pub const TRASH_DIR: &str = ".syncrab-trash"; // customisable, inside the job's target
+pub const TRASH_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+pub const HEARTBEAT_SECS: u64 = 30; // customisable, how often syncrab_w tells it is alive
+pub const HEARTBEAT_STALE_SECS: i64 = 90; // customisable, silence after which a running syncrab_w is stale
+pub const HEARTBEAT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+pub const PROC: &str = "/proc";
+pub const SHUTDOWN_GRACE_SECS: u64 = 10; // customisable, how long syncrab_w lets its syncs finish when stopped
+pub const SHUTDOWN_CANCEL_SECS: u64 = 5; // customisable, how long it then waits for them to stop after their current file
+// Results that are not runs (e.g. the watcher reloading a job), skipped by a job's last run
+pub const NOTE_PREFIX: &str = "ℹ️";
+pub const WATCHER: &str = "Watcher";
+pub const WATCHING: &str = "watching";
+pub const WATCH_FAILED: &str = "failed";
+pub const ALIVE: &str = "alive";
+pub const STALE: &str = "stale";
+pub const DOWN: &str = "down";
+
+// control socket ─────────────────────────────────────────────
+pub const CONTROL_SOCKET: &str = "syncrab_w.sock"; // next to the DB
+pub const CONTROL_TIMEOUT_MS: u64 = 500; // customisable, how long a client waits for syncrab_w to answer
+pub const WATCHER_REFRESH_MS: u64 = 2000; // customisable, how often the TUI asks syncrab_w for its status
+pub const CTL: &str = "ctl";
+pub const RESYNC: &str = "resync";
+
+// locks ──────────────────────────────────────────────────────
+pub const LOCKS_DIR: &str = "syncrab_locks";
+pub const LOCK_WAIT_SECS: u64 = 0; // customisable, how long syncrab_b waits for a running job (0 skips it)
+pub const LOCK_POLL_MS: u64 = 500;
+pub const CANCEL_EXT: &str = "cancel";
+pub const ABANDON_GRACE_SECS: u64 = 30; // customisable, how long an interrupted run may take to finish its current file before syncrab_b leaves it behind
+
+// retries ────────────────────────────────────────────────────
+pub const RETRY_DELAY: u16 = 5; // customisable, default minutes before the first retry
+pub const RETRY_BACKOFF: f64 = 2.0; // customisable, default factor applied to the delay after each retry
+pub const MAX_RETRIES: u8 = 20; // customisable, most retries a job may ask for
+pub const MAX_RETRY_BACKOFF: f64 = 10.0; // customisable, largest factor a job may apply to its delay
+pub const MAX_RETRY_WAIT_MINUTES: i64 = 10080; // customisable, longest wait before a retry (a week)
+pub const RETRY: &str = "retry";
+pub const DUE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+// schedule policies ──────────────────────────────────────────
+pub const DEFERRED: &str = "deferred";
+pub const WINDOW_SEPARATOR: char = '-';
+
+// target guards ────────────────────────────────────────────
+pub const GUARD_NONE: &str = "none";
+pub const GUARD_MOUNT: &str = "mount";
+pub const GUARD_MARKER: &str = "marker";
+pub const GUARDS: [&str; 3] = [GUARD_NONE, GUARD_MOUNT, GUARD_MARKER];
+pub const TARGET_MARKER: &str = ".syncrab-target"; // customisable
+pub const MOUNTINFO: &str = "/proc/self/mountinfo";
+
+// watch backends ─────────────────────────────────────────────
+pub const WATCH_NATIVE: &str = "native";
+pub const WATCH_POLLING: &str = "polling";
+pub const WATCH_BACKENDS: [&str; 2] = [WATCH_NATIVE, WATCH_POLLING];
+pub const POLL_INTERVAL_SECS: u16 = 30; // customisable, default time between two scans of a polled source
+
+// mount triggers ─────────────────────────────────────────────
+pub const PER_MOUNT: &str = "mount";
+pub const PER_DAY: &str = "day";
+pub const UUID_PREFIX: &str = "UUID=";
+pub const DISK_BY_UUID: &str = "/dev/disk/by-uuid";
+pub const LOG_DATE_FORMAT: &str = "%d-%m-%Y";
+pub const LOG_STAMP_FORMAT: &str = "%d-%m-%Y %H:%M";
+
 // db ─────────────────────────────────────────────────────────
 pub const DB_NAME: &str = "syncrab.db";
+pub const DB_BUSY_TIMEOUT_MS: u64 = 5000; // customisable, how long a connection waits for another one's write
+pub const DB_BACKUP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S"; // suffix of the copy made before upgrading the schema
+
+// journal retention ──────────────────────────────────────────
+pub const RETENTION_DAYS: i64 = 30; // customisable, logs younger than this are kept
+pub const RETENTION_RUNS_PER_JOB: usize = 20; // customisable, each job's latest runs are kept however old
+pub const RETENTION_FAILURE_DAYS: i64 = 90; // customisable, failed runs are kept this long
+pub const PRUNE_INTERVAL_HOURS: i64 = 24; // customisable, how often syncrab_b & syncrab_w prune the journal
+pub const VACUUM_FREE_RATIO: f64 = 0.25; // customisable, share of unused pages above which pruning vacuums the DB
 
 // components ─────────────────────────────────────────────────
 pub const COL_GREEN: Color = Color::Rgb(125, 176, 136);
@@ -50,7 +159,11 @@ pub const ACTION_ACTIVE: &str = "⏯️ [Space] Toggle Active";
 pub const ACTION_DISABLE: &str = "🛑 [Alt+Space] Disable All";
 pub const ACTION_ENABLE: &str = "✅ [Ctrl+Space] Enable All";
 pub const ACTION_CLONE: &str = "📄📄 [Ctrl+C] Clone";
+pub const ACTION_CANCEL: &str = "⛔ [x] Cancel Run";
+pub const ACTION_GUARD: &str = "💽 [g] Cycle Guard";
 pub const ACTION_UPDATE: &str = "💾 [Enter] Update";
+pub const ACTION_DAYS: &str = "📅 [←→] Pick Day";
+pub const ACTION_TOGGLE_DAY: &str = "☑️ [Space] Toggle Day";
 pub const ACTION_EDIT: &str = "📝 [Enter] Edit";
 pub const ACTION_VIEW: &str = "👀 [Enter] View";
 pub const ACTION_QUIT: &str = "❌ [q] Quit";
@@ -76,13 +189,20 @@ pub const APP_SUBTITLE: &str = "Manage and monitor your backup jobs";
 pub const REAL_TIME_BACKUPS: &str = "Real-time Backups";
 pub const DAILY_BACKUPS: &str = "Daily Backups";
 pub const WEEKLY_BACKUPS: &str = "Weekly Backups";
+pub const MOUNT_BACKUPS: &str = "On-mount Backups";
 
 pub const SEARCH: &str = "search";
 pub const FILTER: &str = "filter";
+pub const SORT: &str = "sort";
+
+pub const LAST_RUN: &str = "last run";
+pub const NEXT_RUN: &str = "next run";
+pub const LAST_STATUS: &str = "last status";
 
 pub const REAL_TIME: &str = "realtime";
 pub const DAILY: &str = "daily";
 pub const WEEKLY: &str = "weekly";
+pub const MOUNT: &str = "mount";
 
 pub const JOURNAL: &str = "journal";
 pub const LOG: &str = "log";
@@ -90,12 +210,22 @@ pub const LOG: &str = "log";
 pub const SUCCESS: &str = "success";
 pub const FAILED: &str = "failed";
 pub const PARTIAL: &str = "partial";
+pub const CANCELLED: &str = "cancelled";
+pub const TIMED_OUT: &str = "timed out";
 
 pub const ID: &str = "id";
 pub const SOURCE: &str = "source";
 pub const TARGET: &str = "target";
-pub const HOUR: &str = "hour";
-pub const DAY: &str = "day";
+pub const TIME: &str = "time";
+pub const DAYS: &str = "days";
+pub const RETRIES: &str = "retries";
+pub const DELAY: &str = "delay (min)";
+pub const BACKOFF: &str = "backoff";
+pub const TIMEOUT: &str = "timeout (min)";
+pub const TRIGGER: &str = "trigger (path or uuid)";
+pub const ONCE_PER: &str = "once per (mount/day)";
+pub const BACKEND: &str = "watch (native/polling)";
+pub const INTERVAL: &str = "poll every (s)";
 
 pub const REPLACE: &str = "replace";
 pub const TO_REPLACE: &str = "text to replace";
@@ -106,16 +236,28 @@ pub const ALL: &str = "all";
 pub const ACTIVE: &str = "active";
 pub const INACTIVE: &str = "inactive";
 
-pub const REAL_TIME_COLS: &[&str; 4] = &["Id", "Source", "Target", "Active"];
-pub const DAILY_COLS: &[&str; 6] = &["Id", "Source", "Target", "Hour", "Mirror", "Active"];
-pub const WEEKLY_COLS: &[&str; 7] = &["Id", "Source", "Target", "Hour", "Day", "Mirror", "Active"];
-pub const JOURNAL_COLS: &[&str; 6] = &[
+pub const REAL_TIME_COLS: &[&str; 9] = &[
+    "Id", "Source", "Target", "Mirror", "Guard", "Last run", "Status", "Watcher", "Active",
+];
+pub const DAILY_COLS: &[&str; 10] = &[
+    "Id", "Source", "Target", "Time", "Mirror", "Guard", "Last run", "Status", "Next run", "Active",
+];
+pub const WEEKLY_COLS: &[&str; 11] = &[
+    "Id", "Source", "Target", "Time", "Days", "Mirror", "Guard", "Last run", "Status", "Next run",
+    "Active",
+];
+pub const MOUNT_COLS: &[&str; 10] = &[
+    "Id", "Source", "Target", "Trigger", "Once per", "Mirror", "Guard", "Last run", "Status",
+    "Active",
+];
+pub const JOURNAL_COLS: &[&str; 7] = &[
     "Id",
     "Started at",
     "Ended at",
     "Status",
     "Jobs Succeeded",
     "Jobs Failed",
+    "Attempt",
 ];
 pub const LOG_COLS: &[&str; 4] = &["Type", "Source", "Target", "Message"];
 
@@ -126,12 +268,31 @@ pub const EMOJI_INACTIVE: &str = "❌";
 pub const EMOJI_STATUS_SUCCESS: &str = "✅";
 pub const EMOJI_STATUS_FAILED: &str = "❌";
 pub const EMOJI_STATUS_PARTIAL: &str = "⚠️";
+pub const EMOJI_STATUS_CANCELLED: &str = "⛔";
+pub const EMOJI_STATUS_TIMED_OUT: &str = "⌛";
+pub const EMOJI_PAUSED: &str = "⏸️";
+pub const EMOJI_BLACKOUT: &str = "🚫";
+pub const EMOJI_RUNNING: &str = "▶️";
+pub const EMOJI_GUARD_MOUNT: &str = "💽";
+pub const EMOJI_GUARD_MARKER: &str = "🏷️";
+pub const EMOJI_WATCHING: &str = "👀";
+pub const EMOJI_POLLING: &str = "🔁";
+pub const EMOJI_QUEUED: &str = "⏳";
+pub const EMOJI_ERRORS: &str = "❗";
+pub const EMOJI_WATCH_FAILED: &str = "💥";
+pub const EMOJI_STALE: &str = "🐌";
+pub const EMOJI_DOWN: &str = "💀";
+pub const SCHEDULE: &str = "Schedule";
+pub const PAUSED: &str = "paused";
+pub const RUNNING: &str = "running";
 pub const EMOJI_STATUS_OTHER: &str = "📊";
 
 pub const EMOJI_STATS: &str = "🗓️";
 pub const EMOJI_SECTION: &str = "🕐";
 pub const EMOJI_SEARCH: &str = "🔭";
 pub const EMOJI_FILTER: &str = "🔍";
+pub const EMOJI_SORT: &str = "🔃";
 
 // week days ──────────────────────────────────────────────────
-pub const WEEK_DAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+pub const WEEK_DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+pub const DAYS_SEPARATOR: char = ',';
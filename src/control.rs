@@ -0,0 +1,148 @@
+// Standards ─────────────────────────────────────────────────────
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    time::Duration,
+};
+
+// Crates ────────────────────────────────────────────────────────
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+// mods ──────────────────────────────────────────────────────────
+use crate::{
+    consts::{CONTROL_SOCKET, CONTROL_TIMEOUT_MS, PAUSE, RESUME, RESYNC, STATUS},
+    db::db::db_path,
+    structs::WatchStatus,
+};
+
+// Protocol ──────────────────────────────────────────────────────
+
+// One JSON object per line each way, e.g. {"command":"pause","job_id":3}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "command", rename_all = "lowercase")]
+pub enum Request {
+    Status,
+    Pause { job_id: u16 },
+    Resume { job_id: u16 },
+    Resync { job_id: u16 },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "response", rename_all = "lowercase")]
+pub enum Response {
+    Status { jobs: Vec<WatchStatus> },
+    Done { message: String },
+    Error { message: String },
+}
+
+impl Request {
+    // `args`: status | pause <job id> | resume <job id> | resync <job id>
+    pub fn parse(args: &[String]) -> Option<Self> {
+        let command = args.first()?.to_lowercase();
+        let job_id = args.get(1).and_then(|arg| arg.parse::<u16>().ok());
+
+        match (command.as_str(), job_id) {
+            (STATUS, None) => Some(Request::Status),
+            (PAUSE, Some(job_id)) => Some(Request::Pause { job_id }),
+            (RESUME, Some(job_id)) => Some(Request::Resume { job_id }),
+            (RESYNC, Some(job_id)) => Some(Request::Resync { job_id }),
+            _ => None,
+        }
+    }
+}
+
+// Client ────────────────────────────────────────────────────────
+pub fn send(request: &Request) -> Result<Response, String> {
+    let path = socket_path();
+    let stream = UnixStream::connect(&path).map_err(|e| {
+        format!(
+            "❌ Could not reach syncrab_w at [{}] because [{}]",
+            path.display(),
+            e
+        )
+    })?;
+
+    let timeout = Some(Duration::from_millis(CONTROL_TIMEOUT_MS));
+    let _ = stream.set_read_timeout(timeout);
+    let _ = stream.set_write_timeout(timeout);
+
+    write_message(&stream, request)?;
+    read_message(&stream)
+}
+
+// The status of every watched job by id, None when syncrab_w does not answer
+pub fn watch_statuses() -> Option<HashMap<u16, WatchStatus>> {
+    match send(&Request::Status) {
+        Ok(Response::Status { jobs }) => Some(
+            jobs.into_iter()
+                .map(|status| (status.job_id, status))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+// `syncrab_w ctl …`
+pub fn run_ctl(args: &[String]) -> Result<(), String> {
+    let request = Request::parse(args).ok_or_else(|| {
+        format!(
+            "❌ Usage: syncrab_w ctl [{} | {} <job id> | {} <job id> | {} <job id>]",
+            STATUS, PAUSE, RESUME, RESYNC
+        )
+    })?;
+
+    match send(&request)? {
+        Response::Status { jobs } if jobs.is_empty() => println!("💤 No job is being watched"),
+        Response::Status { jobs } => {
+            for status in jobs {
+                println!(
+                    "{} [{}] {} → {}",
+                    status.formatted(),
+                    status.job_id,
+                    status.source,
+                    status.target
+                );
+                println!(
+                    "   {} watch, last event: {}, last sync: {}, syncs: {}, errors: {}, queued: {}",
+                    status.backend,
+                    status.last_event.as_deref().unwrap_or("-"),
+                    status.last_sync.as_deref().unwrap_or("-"),
+                    status.syncs,
+                    status.errors,
+                    status.queued
+                );
+            }
+        }
+        Response::Done { message } => println!("{}", message),
+        Response::Error { message } => return Err(message),
+    }
+
+    Ok(())
+}
+
+// Helpers ───────────────────────────────────────────────────────
+pub fn socket_path() -> PathBuf {
+    db_path().with_file_name(CONTROL_SOCKET)
+}
+
+pub fn write_message<T: Serialize>(mut stream: &UnixStream, message: &T) -> Result<(), String> {
+    let mut line = serde_json::to_string(message)
+        .map_err(|e| format!("❌ Could not encode the message because [{}]", e))?;
+    line.push('\n');
+
+    stream
+        .write_all(line.as_bytes())
+        .map_err(|e| format!("❌ Could not send the message because [{}]", e))
+}
+
+pub fn read_message<T: DeserializeOwned>(stream: &UnixStream) -> Result<T, String> {
+    let mut line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut line)
+        .map_err(|e| format!("❌ Could not read the message because [{}]", e))?;
+
+    serde_json::from_str(&line)
+        .map_err(|e| format!("❌ Could not decode [{}] because [{}]", line.trim(), e))
+}
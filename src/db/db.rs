@@ -1,26 +1,41 @@
 // Standards ─────────────────────────────────────────────────────
-use std::{collections::HashMap, env, path::PathBuf};
+use std::{collections::HashMap, env, path::PathBuf, process, time::Duration};
 
 // Crates ────────────────────────────────────────────────────────
-use rusqlite::{Connection, Transaction};
+use chrono::NaiveDateTime;
+use rusqlite::{Connection, Transaction, params, params_from_iter, types::Value};
 
 // mods ──────────────────────────────────────────────────────────
 use crate::{
-    consts::{ACTIVE, ALL, DAILY, DB_NAME, INACTIVE, REAL_TIME, WEEKLY},
-    structs::{Job, Log, LogResult},
+    consts::{
+        CANCELLED, DAILY, DAYS_SEPARATOR, DB_BUSY_TIMEOUT_MS, DB_NAME, FAILED, GUARD_NONE,
+        LOG_DATE_FORMAT, LOG_STAMP_FORMAT, MOUNT, NOTE_PREFIX, PER_DAY, PER_MOUNT, POLL_INTERVAL_SECS,
+        REAL_TIME, SUCCESS, TIMED_OUT, VACUUM_FREE_RATIO, WATCH_NATIVE, WEEKLY,
+    },
+    db::{migrations::migrate, query::JobQuery},
+    structs::{Heartbeat, Job, Log, LogResult, Mount, PendingRun, SchedulePolicy, WatchState},
+    utils::{blocked_until, find_trigger_mount, get_mounts, mount_key},
 };
 
 // DB Helpers ─────────────────────────────────────────────────────
 fn query_all<T, F>(conn: &Connection, sql: &str, mapper: F) -> Vec<T>
 where
     F: Fn(&rusqlite::Row) -> rusqlite::Result<T>,
+{
+    query_all_with(conn, sql, [], mapper)
+}
+
+fn query_all_with<T, F, P>(conn: &Connection, sql: &str, params: P, mapper: F) -> Vec<T>
+where
+    F: Fn(&rusqlite::Row) -> rusqlite::Result<T>,
+    P: rusqlite::Params,
 {
     let mut stmt = conn
         .prepare(sql)
         .unwrap_or_else(|e| panic!("❌ Failed to prepare statement because [{}]", e));
 
     let iter = stmt
-        .query_map([], mapper)
+        .query_map(params, mapper)
         .unwrap_or_else(|e| panic!("❌ Failed to execute query because [{}]", e));
 
     iter.map(|result| {
@@ -92,79 +107,90 @@ pub fn db_path() -> PathBuf {
 }
 
 fn db_connect() -> Connection {
-    Connection::open(db_path())
-        .unwrap_or_else(|e| panic!("❌ Failed to connect to the database because [{}]", e))
+    let conn = Connection::open(db_path())
+        .unwrap_or_else(|e| panic!("❌ Failed to connect to the database because [{}]", e));
+
+    // The binaries share the file: wait for another one's write rather than fail.
+    // SQLite leaves foreign keys unenforced unless each connection asks
+    conn.busy_timeout(Duration::from_millis(DB_BUSY_TIMEOUT_MS))
+        .and_then(|_| conn.pragma_update(None, "foreign_keys", true))
+        .unwrap_or_else(|e| panic!("❌ Failed to configure the database connection because [{}]", e));
+
+    conn
 }
 
+// Exits rather than panics when the database cannot be brought up to date
 pub fn init_db() -> Connection {
-    let conn: Connection = db_connect();
+    let mut conn: Connection = db_connect();
 
-    // Create Jobs table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS jobs (
-            id          INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
-            frequency   TEXT NOT NULL,
-            hour        NUMERIC,
-            day         TEXT,
-            source      TEXT NOT NULL,
-            target      TEXT NOT NULL,
-            mirror      INTEGER DEFAULT 1,
-            active      INTEGER DEFAULT 0
-        )",
-        [],
-    )
-    .unwrap_or_else(|e| panic!("❌ Failed to create the jobs table because [{}]", e));
+    if let Err(error) = migrate(&mut conn, &db_path()) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
 
-    // Create Logs table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS logs (
-            id                      INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
-            startstamp              TEXT NOT NULL,
-            endstamp                TEXT NOT NULL,
-            status                  TEXT NOT NULL,
-            success_count           INTEGER NOT NULL,
-            failed_count            INTEGER NOT NULL
-        )",
-        [],
-    )
-    .unwrap_or_else(|e| panic!("❌ Failed to create the logs table because [{}]", e));
+    conn
+}
 
-    // Create LogResults table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS log_results (
-            log_id           INTEGER NOT NULL,
-            frequency        TEXT NOT NULL,
-            message          TEXT NOT NULL,
-            source           TEXT NOT NULL,
-            target           TEXT NOT NULL
-        )",
-        [],
-    )
-    .unwrap_or_else(|e| panic!("❌ Failed to create the log_results table because [{}]", e));
+fn job_from_row(row: &rusqlite::Row) -> rusqlite::Result<Job> {
+    Ok(Job {
+        id: row.get("id")?,
+        frequency: row.get("frequency")?,
+        days: Job::parse_days(row.get("day")?),
+        hour: row.get("hour")?,
+        minute: row.get("minute")?,
+        source: row.get("source")?,
+        target: row.get("target")?,
+        mirror: row.get("mirror")?,
+        active: row.get("active")?,
+        retry_max: row.get("retry_max")?,
+        retry_delay: row.get("retry_delay")?,
+        retry_backoff: row.get("retry_backoff")?,
+        timeout: row.get("timeout")?,
+        target_guard: row
+            .get::<_, Option<String>>("target_guard")?
+            .unwrap_or_else(|| GUARD_NONE.to_string()),
+        mount_trigger: row
+            .get::<_, Option<String>>("mount_trigger")?
+            .unwrap_or_default(),
+        once_per: row
+            .get::<_, Option<String>>("once_per")?
+            .unwrap_or_else(|| PER_MOUNT.to_string()),
+        watch_backend: row
+            .get::<_, Option<String>>("watch_backend")?
+            .unwrap_or_else(|| WATCH_NATIVE.to_string()),
+        poll_interval: row
+            .get::<_, Option<u16>>("poll_interval")?
+            .unwrap_or(POLL_INTERVAL_SECS),
+        last_run: None,
+        last_status: None,
+        watch_status: None,
+        watch_state: None,
+    })
+}
 
-    conn
+pub fn get_job(conn: &Connection, id: u16) -> Option<Job> {
+    conn.query_row("SELECT * FROM jobs WHERE id = ?1", (id,), job_from_row)
+        .ok()
 }
 
-fn get_jobs(conn: &Connection, sql: &str) -> HashMap<&'static str, Vec<Job>> {
-    let mut jobs_by_freq: HashMap<&'static str, Vec<Job>> = HashMap::with_capacity(3);
+pub fn find_jobs(conn: &Connection, query: &JobQuery) -> Vec<Job> {
+    let (clause, params) = query.to_sql();
+    query_all_with(
+        conn,
+        &format!("SELECT * FROM jobs WHERE {} ORDER BY id;", clause),
+        params_from_iter(params),
+        job_from_row,
+    )
+}
+
+fn get_jobs(conn: &Connection, query: &JobQuery) -> HashMap<&'static str, Vec<Job>> {
+    let mut jobs_by_freq: HashMap<&'static str, Vec<Job>> = HashMap::with_capacity(4);
     jobs_by_freq.insert(REAL_TIME, Vec::new());
     jobs_by_freq.insert(DAILY, Vec::new());
     jobs_by_freq.insert(WEEKLY, Vec::new());
+    jobs_by_freq.insert(MOUNT, Vec::new());
 
-    let jobs = query_all(conn, sql, |row| {
-        Ok(Job {
-            id: row.get("id")?,
-            frequency: row.get("frequency")?,
-            day: row.get("day")?,
-            hour: row.get("hour")?,
-            source: row.get("source")?,
-            target: row.get("target")?,
-            mirror: row.get("mirror")?,
-            active: row.get("active")?,
-        })
-    });
-
-    for job in jobs {
+    for job in find_jobs(conn, query) {
         jobs_by_freq
             .get_mut(job.frequency.as_str())
             .unwrap()
@@ -175,71 +201,177 @@ fn get_jobs(conn: &Connection, sql: &str) -> HashMap<&'static str, Vec<Job>> {
 }
 
 pub fn get_all_jobs(conn: &Connection) -> HashMap<&'static str, Vec<Job>> {
-    let sql = "SELECT * FROM jobs;";
-    get_jobs(&conn, &sql)
+    let mut jobs_by_freq = get_jobs(conn, &JobQuery::default());
+
+    let last_runs = get_last_runs(conn);
+    for job in jobs_by_freq.values_mut().flatten() {
+        if let Some((last_run, last_status)) = last_runs.get(&job.id.unwrap()) {
+            job.last_run = Some(*last_run);
+            job.last_status = Some(last_status.to_string());
+        }
+    }
+
+    jobs_by_freq
 }
 
+// When each job last ran and how it went, from its latest result in the journal
+pub fn get_last_runs(conn: &Connection) -> HashMap<u16, (NaiveDateTime, String)> {
+    let rows = query_all(
+        conn,
+        "SELECT lr.job_id, l.startstamp, l.status, lr.message
+        FROM log_results lr JOIN logs l ON l.id = lr.log_id
+        WHERE lr.job_id IS NOT NULL
+        ORDER BY l.id, lr.rowid;",
+        |row| {
+            Ok((
+                row.get::<_, u16>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        },
+    );
+
+    let mut last_runs = HashMap::new();
+    for (job_id, startstamp, log_status, message) in rows {
+        let Ok(last_run) = NaiveDateTime::parse_from_str(&startstamp, LOG_STAMP_FORMAT) else {
+            continue;
+        };
+
+        // Notes (e.g. the watcher reloading or stopping) are not runs
+        if message.starts_with(NOTE_PREFIX) {
+            continue;
+        }
+
+        // An interrupted run tells why the job did not finish
+        // Successful results start with "OK", real-time ones add what they synced
+        let status = match log_status.as_str() {
+            _ if message.starts_with("OK") => SUCCESS,
+            CANCELLED => CANCELLED,
+            TIMED_OUT => TIMED_OUT,
+            _ => FAILED,
+        };
+
+        last_runs.insert(job_id, (last_run, status.to_string()));
+    }
+
+    last_runs
+}
+
+// `query`: None for the scheduled (argument-less) run, which picks the jobs set to run now.
+// Also returns when the schedule policies stop blocking the scheduled run, if they block it
+// right now. Explicit runs ignore the policies.
+// The scheduled run also picks the on-mount jobs whose drive showed up since the last run
 pub fn get_jobs_to_run(
     conn: &Connection,
-    args: Option<(String, Option<String>)>,
-    day: String,
-    hour: u8,
-) -> HashMap<&'static str, Vec<Job>> {
-    let sql = match args {
-        None => {
-            format!(
-                "SELECT * FROM jobs WHERE active = 1 AND (
-                    (frequency = '{daily}' AND hour = {hour}) 
-                    OR (frequency = '{weekly}' AND day = '{day}' AND hour = {hour}) 
-                );",
-                daily = DAILY,
-                weekly = WEEKLY,
-                hour = hour,
-                day = day,
-            )
-        }
+    query: Option<JobQuery>,
+    now: &NaiveDateTime,
+) -> (HashMap<&'static str, Vec<Job>>, Option<NaiveDateTime>) {
+    let Some(query) = query else {
+        let mut jobs = get_jobs(conn, &JobQuery::scheduled(now));
+        jobs.insert(MOUNT, get_mounted_jobs(conn, now));
+        return (jobs, blocked_until(&get_policies(conn), now));
+    };
 
-        Some((arg1, arg2)) => {
-            if arg1 == ALL {
-                match arg2.as_deref() {
-                    Some(ACTIVE) => "SELECT * FROM jobs WHERE active = 1;".to_string(),
-                    Some(INACTIVE) => "SELECT * FROM jobs WHERE active = 0;".to_string(),
-                    _ => "SELECT * FROM jobs;".to_string(),
-                }
-            } else {
-                match arg2.as_deref() {
-                    Some(ACTIVE) => format!(
-                        "SELECT * FROM jobs WHERE frequency = '{freq}' AND active = 1;",
-                        freq = arg1
-                    ),
-                    Some(INACTIVE) => format!(
-                        "SELECT * FROM jobs WHERE frequency = '{freq}' AND active = 0;",
-                        freq = arg1
-                    ),
-                    _ => format!(
-                        "SELECT * FROM jobs WHERE frequency = '{freq}';",
-                        freq = arg1
-                    ),
-                }
-            }
+    (get_jobs(conn, &query), None)
+}
+
+fn get_mounted_jobs(conn: &Connection, now: &NaiveDateTime) -> Vec<Job> {
+    let mounts = match get_mounts() {
+        Ok(mounts) => mounts,
+        Err(error) => {
+            eprintln!("❌ Could not look for mounted drives because [{}]", error);
+            return Vec::new();
         }
     };
 
-    get_jobs(&conn, &sql)
+    let query = JobQuery {
+        frequencies: vec![MOUNT.into()],
+        active: Some(true),
+        ..Default::default()
+    };
+    let today = now.format(LOG_DATE_FORMAT).to_string();
+
+    find_jobs(conn, &query)
+        .into_iter()
+        .filter(|job| {
+            let job_id = job.id.unwrap();
+            let mount = find_trigger_mount(&job.mount_trigger, &mounts);
+            let mount_key = mount.map(mount_key);
+            let (seen_id, seen_key) = get_mount_state(conn, job_id);
+
+            if (seen_id, &seen_key) != (mount.map(|mount| mount.id), &mount_key)
+                && let Err(error) = set_mount_state(conn, job_id, mount, mount_key.as_deref())
+            {
+                eprintln!("{}", error);
+            }
+
+            // Still the same mount, or still unplugged. Rows saved before the keys only hold an id
+            let is_same = match seen_key {
+                Some(_) => seen_key == mount_key,
+                None => seen_id == mount.map(|mount| mount.id),
+            };
+            if is_same {
+                return false;
+            }
+
+            mount.is_some() && !(job.once_per == PER_DAY && has_run_on(conn, job_id, &today))
+        })
+        .collect()
+}
+
+// The mount id & key the job last saw, both None while its drive is unplugged
+fn get_mount_state(conn: &Connection, job_id: u16) -> (Option<u32>, Option<String>) {
+    conn.query_row(
+        "SELECT mount_id, mount_key FROM mount_states WHERE job_id = ?1",
+        (job_id,),
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .unwrap_or((None, None))
+}
+
+fn set_mount_state(
+    conn: &Connection,
+    job_id: u16,
+    mount: Option<&Mount>,
+    mount_key: Option<&str>,
+) -> Result<usize, String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO mount_states (job_id, mount_id, mount_key) VALUES (?1, ?2, ?3)",
+        (job_id, mount.map(|mount| mount.id), mount_key),
+    )
+    .map_err(|e| format!("❌ Failed to save the mount state because [{}]", e))
+}
+
+// `day` in the logs' startstamp format, e.g. "18-10-2026"
+fn has_run_on(conn: &Connection, job_id: u16, day: &str) -> bool {
+    conn.query_row(
+        "SELECT EXISTS(
+            SELECT 1 FROM log_results lr JOIN logs l ON l.id = lr.log_id
+            WHERE lr.job_id = ?1 AND l.startstamp LIKE ?2 || '%'
+        )",
+        (job_id, day),
+        |row| row.get(0),
+    )
+    .unwrap_or(false)
+}
+
+fn log_from_row(row: &rusqlite::Row) -> rusqlite::Result<Log> {
+    Ok(Log {
+        id: row.get("id")?,
+        startstamp: row.get("startstamp")?,
+        endstamp: row.get("endstamp")?,
+        status: row.get("status")?,
+        success_count: row.get("success_count")?,
+        failed_count: row.get("failed_count")?,
+        retry_of: row.get("retry_of")?,
+        attempt: row.get("attempt")?,
+        log_results: None,
+    })
 }
 
 pub fn get_logs(conn: &Connection) -> Vec<Log> {
-    let mut logs = query_all(conn, "SELECT * FROM logs;", |row| {
-        Ok(Log {
-            id: row.get("id")?,
-            startstamp: row.get("startstamp")?,
-            endstamp: row.get("endstamp")?,
-            status: row.get("status")?,
-            success_count: row.get("success_count")?,
-            failed_count: row.get("failed_count")?,
-            log_results: None,
-        })
-    });
+    let mut logs = query_all(conn, "SELECT * FROM logs;", log_from_row);
 
     let log_results = get_log_results(conn);
     let mut results_map: HashMap<u16, Vec<LogResult>> = HashMap::new();
@@ -264,6 +396,7 @@ pub fn get_log_results(conn: &Connection) -> Vec<LogResult> {
     query_all(conn, "SELECT * FROM log_results;", |row| {
         Ok(LogResult {
             log_id: row.get("log_id")?,
+            job_id: row.get("job_id")?,
             frequency: row.get("frequency")?,
             message: row.get("message")?,
             source: row.get("source")?,
@@ -272,19 +405,88 @@ pub fn get_log_results(conn: &Connection) -> Vec<LogResult> {
     })
 }
 
+// What the retention policy weighs: every log, without its results, and the jobs each one ran
+pub fn get_log_entries(conn: &Connection) -> (Vec<Log>, Vec<(u16, Option<u16>)>) {
+    let logs = query_all(conn, "SELECT * FROM logs ORDER BY id;", log_from_row);
+    let log_jobs = query_all(conn, "SELECT log_id, job_id FROM log_results;", |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    });
+
+    (logs, log_jobs)
+}
+
+// Their results go along (ON DELETE CASCADE)
+pub fn delete_logs(conn: &mut Connection, ids: &[u16]) -> Result<usize, String> {
+    execute_transaction(conn, "delete logs", |transaction| {
+        let mut stmt = transaction.prepare("DELETE FROM logs WHERE id = ?1")?;
+
+        for id in ids {
+            stmt.execute((id,))?;
+        }
+
+        Ok(())
+    })?;
+
+    Ok(ids.len())
+}
+
+pub fn get_last_prune(conn: &Connection) -> Option<String> {
+    conn.query_row("SELECT pruned_at FROM maintenance WHERE id = 1", [], |row| {
+        row.get(0)
+    })
+    .ok()
+    .flatten()
+}
+
+pub fn set_last_prune(conn: &mut Connection, pruned_at: &str) -> Result<usize, String> {
+    execute_sql(
+        conn,
+        "INSERT OR REPLACE INTO maintenance (id, pruned_at) VALUES (1, ?1)",
+        (pruned_at,),
+        "save when the journal was pruned",
+    )
+}
+
+// Gives the pages freed by deletions back once they make up VACUUM_FREE_RATIO of the file
+pub fn vacuum_if_needed(conn: &mut Connection) -> Result<bool, String> {
+    let (free, total): (i64, i64) = conn
+        .query_row(
+            "SELECT freelist_count, page_count FROM pragma_freelist_count, pragma_page_count",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| format!("❌ Failed to measure the database because [{}]", e))?;
+
+    if total == 0 || (free as f64) / (total as f64) < VACUUM_FREE_RATIO {
+        return Ok(false);
+    }
+
+    execute_sql(conn, "VACUUM", [], "vacuum the database").map(|_| true)
+}
+
 pub fn insert(conn: &mut Connection, job: &Job) -> Result<usize, String> {
     execute_sql(
         conn,
-        "INSERT INTO jobs (source, target, day, hour, mirror, active, frequency) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-        (
+        "INSERT INTO jobs (source, target, day, hour, minute, mirror, active, frequency, retry_max, retry_delay, retry_backoff, timeout, target_guard, mount_trigger, once_per, watch_backend, poll_interval) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+        params![
             &job.source,
             &job.target,
-            &job.day.as_ref(),
-            &job.hour.clone(),
+            &job.days_to_string(),
+            &job.hour,
+            &job.minute,
             &job.mirror,
             &job.active,
             &job.frequency,
-        ),
+            &job.retry_max,
+            &job.retry_delay,
+            &job.retry_backoff,
+            &job.timeout,
+            &job.target_guard,
+            &job.mount_trigger,
+            &job.once_per,
+            &job.watch_backend,
+            &job.poll_interval,
+        ],
         "create the job record",
     ).map(|_| conn.last_insert_rowid() as usize)
 }
@@ -292,17 +494,27 @@ pub fn insert(conn: &mut Connection, job: &Job) -> Result<usize, String> {
 pub fn update(conn: &mut Connection, job: &Job) -> Result<usize, String> {
     execute_sql(
         conn,
-        "UPDATE jobs SET source = ?1, target = ?2, day = ?3, hour = ?4, mirror = ?5, active = ?6, frequency = ?7 WHERE id = ?8",
-        (
+        "UPDATE jobs SET source = ?1, target = ?2, day = ?3, hour = ?4, minute = ?5, mirror = ?6, active = ?7, frequency = ?8, retry_max = ?9, retry_delay = ?10, retry_backoff = ?11, timeout = ?12, target_guard = ?13, mount_trigger = ?14, once_per = ?15, watch_backend = ?16, poll_interval = ?17 WHERE id = ?18",
+        params![
             &job.source,
             &job.target,
-            &job.day.as_ref(),
-            &job.hour.clone(),
+            &job.days_to_string(),
+            &job.hour,
+            &job.minute,
             &job.mirror,
             &job.active,
             &job.frequency,
+            &job.retry_max,
+            &job.retry_delay,
+            &job.retry_backoff,
+            &job.timeout,
+            &job.target_guard,
+            &job.mount_trigger,
+            &job.once_per,
+            &job.watch_backend,
+            &job.poll_interval,
             &job.id,
-        ),
+        ],
         "update the job record",
     )
 }
@@ -316,24 +528,16 @@ pub fn delete(conn: &mut Connection, id: u16) -> Result<usize, String> {
     )
 }
 
-pub fn mass_update(
-    conn: &mut Connection,
-    ids: &[u16],
-    update_field: &str,
-    toggle_value: u8,
-) -> Result<usize, String> {
-    execute_transaction(conn, "update jobs", |transaction| {
-        let query = format!("UPDATE jobs SET {} = ? WHERE id = ?", update_field);
-        let mut stmt = transaction.prepare(&query)?;
-
-        for id in ids {
-            stmt.execute((toggle_value, id))?;
-        }
+pub fn set_active(conn: &mut Connection, query: &JobQuery, active: u8) -> Result<usize, String> {
+    let (clause, mut params) = query.to_sql();
+    params.push(Value::Integer(active as i64));
 
-        Ok(())
-    })?;
-
-    Ok(1)
+    execute_sql(
+        conn,
+        &format!("UPDATE jobs SET active = ?{} WHERE {}", params.len(), clause),
+        params_from_iter(params),
+        "update jobs",
+    )
 }
 
 pub fn mass_replace(conn: &mut Connection, jobs: Vec<&mut Job>) -> Result<usize, String> {
@@ -355,13 +559,15 @@ pub fn mass_replace(conn: &mut Connection, jobs: Vec<&mut Job>) -> Result<usize,
 pub fn insert_log(conn: &mut Connection, log: Log) -> Result<usize, String> {
     execute_sql(
         conn,
-        "INSERT INTO logs (startstamp, endstamp, status, success_count, failed_count) VALUES (?1, ?2, ?3, ?4, ?5)",
+        "INSERT INTO logs (startstamp, endstamp, status, success_count, failed_count, retry_of, attempt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
         (
             &log.startstamp,
             &log.endstamp,
             &log.status,
             &log.success_count,
             &log.failed_count,
+            &log.retry_of,
+            &log.attempt,
         ),
         "create the log record",
     ).map(|_| conn.last_insert_rowid() as usize)
@@ -377,12 +583,13 @@ pub fn insert_log_resuts(
         &format!("insert log_results for log_id [{}]", log_id),
         |transaction| {
             let mut stmt = transaction.prepare(
-            "INSERT INTO log_results (log_id, frequency, message, source, target) VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT INTO log_results (log_id, job_id, frequency, message, source, target) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
         )?;
 
             for log_result in log_results {
                 stmt.execute((
                     &log_id,
+                    &log_result.job_id,
                     &log_result.frequency,
                     &log_result.message,
                     &log_result.source,
@@ -396,3 +603,179 @@ pub fn insert_log_resuts(
 
     Ok(1)
 }
+
+pub fn insert_pending_run(
+    conn: &mut Connection,
+    pending_run: &PendingRun,
+) -> Result<usize, String> {
+    execute_sql(
+        conn,
+        "INSERT INTO pending_runs (job_id, due_at, attempt, origin_log_id, reason) VALUES (?1, ?2, ?3, ?4, ?5)",
+        (
+            &pending_run.job_id,
+            &pending_run.due_at,
+            &pending_run.attempt,
+            &pending_run.origin_log_id,
+            &pending_run.reason,
+        ),
+        "create the pending run record",
+    )
+    .map(|_| conn.last_insert_rowid() as usize)
+}
+
+// Fetch and remove every pending run whose time has come
+pub fn take_due_runs(conn: &mut Connection, now: &str) -> Result<Vec<PendingRun>, String> {
+    let mut due_runs = Vec::new();
+
+    execute_transaction(conn, "take the due pending runs", |transaction| {
+        let mut stmt = transaction
+            .prepare("SELECT * FROM pending_runs WHERE due_at <= ?1 ORDER BY due_at, id")?;
+
+        due_runs = stmt
+            .query_map((now,), |row| {
+                Ok(PendingRun {
+                    id: row.get("id")?,
+                    job_id: row.get("job_id")?,
+                    due_at: row.get("due_at")?,
+                    attempt: row.get("attempt")?,
+                    origin_log_id: row.get("origin_log_id")?,
+                    reason: row.get("reason")?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        transaction.execute("DELETE FROM pending_runs WHERE due_at <= ?1", (now,))?;
+
+        Ok(())
+    })?;
+
+    Ok(due_runs)
+}
+
+pub fn has_pending_run(conn: &Connection, job_id: u16, reason: &str) -> bool {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM pending_runs WHERE job_id = ?1 AND reason = ?2)",
+        (job_id, reason),
+        |row| row.get(0),
+    )
+    .unwrap_or(false)
+}
+
+pub fn get_policies(conn: &Connection) -> Vec<SchedulePolicy> {
+    query_all(conn, "SELECT * FROM schedule_policies;", |row| {
+        Ok(SchedulePolicy {
+            id: row.get("id")?,
+            kind: row.get("kind")?,
+            days: Job::parse_days(row.get("day")?),
+            start: row.get("start")?,
+            end: row.get("end")?,
+            until: row.get("until")?,
+        })
+    })
+}
+
+pub fn insert_policy(conn: &mut Connection, policy: &SchedulePolicy) -> Result<usize, String> {
+    let days = (!policy.days.is_empty()).then(|| policy.days.join(&DAYS_SEPARATOR.to_string()));
+
+    execute_sql(
+        conn,
+        "INSERT INTO schedule_policies (kind, day, start, end, until) VALUES (?1, ?2, ?3, ?4, ?5)",
+        (
+            &policy.kind,
+            &days,
+            &policy.start,
+            &policy.end,
+            &policy.until,
+        ),
+        "create the schedule policy record",
+    )
+    .map(|_| conn.last_insert_rowid() as usize)
+}
+
+pub fn delete_policy(conn: &mut Connection, id: u16) -> Result<usize, String> {
+    execute_sql(
+        conn,
+        "DELETE FROM schedule_policies WHERE id = ?1",
+        (&id,),
+        "delete the schedule policy record",
+    )
+}
+
+pub fn delete_policies_of_kind(conn: &mut Connection, kind: &str) -> Result<usize, String> {
+    execute_sql(
+        conn,
+        "DELETE FROM schedule_policies WHERE kind = ?1",
+        (kind,),
+        "delete the schedule policy records",
+    )
+}
+
+// What syncrab_w writes so the TUI can tell whether real-time jobs are being synced
+pub fn get_heartbeat(conn: &Connection) -> Option<Heartbeat> {
+    conn.query_row("SELECT * FROM watcher_heartbeat WHERE id = 1", [], |row| {
+        Ok(Heartbeat {
+            pid: row.get("pid")?,
+            started_at: row.get("started_at")?,
+            beat_at: row.get("beat_at")?,
+            watched_count: row.get("watched_count")?,
+            last_event_at: row.get("last_event_at")?,
+        })
+    })
+    .ok()
+}
+
+pub fn set_heartbeat(conn: &mut Connection, heartbeat: &Heartbeat) -> Result<usize, String> {
+    execute_sql(
+        conn,
+        "INSERT OR REPLACE INTO watcher_heartbeat (id, pid, started_at, beat_at, watched_count, last_event_at) VALUES (1, ?1, ?2, ?3, ?4, ?5)",
+        (
+            &heartbeat.pid,
+            &heartbeat.started_at,
+            &heartbeat.beat_at,
+            &heartbeat.watched_count,
+            &heartbeat.last_event_at,
+        ),
+        "save the watcher heartbeat",
+    )
+}
+
+pub fn delete_heartbeat(conn: &mut Connection) -> Result<usize, String> {
+    execute_sql(
+        conn,
+        "DELETE FROM watcher_heartbeat",
+        [],
+        "delete the watcher heartbeat",
+    )
+}
+
+pub fn get_watch_states(conn: &Connection) -> HashMap<u16, WatchState> {
+    query_all(conn, "SELECT * FROM watch_states;", |row| {
+        Ok(WatchState {
+            job_id: row.get("job_id")?,
+            state: row.get("state")?,
+            error: row.get("error")?,
+        })
+    })
+    .into_iter()
+    .map(|state| (state.job_id, state))
+    .collect()
+}
+
+pub fn set_watch_state(conn: &mut Connection, watch_state: &WatchState) -> Result<usize, String> {
+    execute_sql(
+        conn,
+        "INSERT OR REPLACE INTO watch_states (job_id, state, error) VALUES (?1, ?2, ?3)",
+        (&watch_state.job_id, &watch_state.state, &watch_state.error),
+        "save the watch state",
+    )
+}
+
+// `job_id`: None clears every state
+pub fn delete_watch_states(conn: &mut Connection, job_id: Option<u16>) -> Result<usize, String> {
+    execute_sql(
+        conn,
+        "DELETE FROM watch_states WHERE ?1 IS NULL OR job_id = ?1",
+        (&job_id,),
+        "delete the watch states",
+    )
+}
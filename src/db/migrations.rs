@@ -0,0 +1,328 @@
+// Standards ─────────────────────────────────────────────────────
+use std::path::{Path, PathBuf};
+
+// Crates ────────────────────────────────────────────────────────
+use chrono::Local;
+use rusqlite::{Connection, Transaction, TransactionBehavior};
+
+// mods ──────────────────────────────────────────────────────────
+use crate::consts::DB_BACKUP_FORMAT;
+
+// Migrations ────────────────────────────────────────────────────
+// A schema change: applied once, in its own transaction, which also records its version in
+// `PRAGMA user_version`
+struct Migration {
+    version: u32,
+    description: &'static str,
+    apply: fn(&Transaction) -> rusqlite::Result<()>,
+}
+
+// In order. A released step is never edited: a schema change is a new step
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create the tables",
+        apply: create_tables,
+    },
+    Migration {
+        version: 2,
+        description: "tie the log results to their log & index the journal",
+        apply: link_log_results,
+    },
+    Migration {
+        version: 3,
+        description: "remember when the journal was last pruned",
+        apply: create_maintenance,
+    },
+    Migration {
+        version: 4,
+        description: "tell the mounts of an on-mount job's drive apart",
+        apply: key_mount_states,
+    },
+    Migration {
+        version: 5,
+        description: "drop the indexes on the journal's stamps",
+        apply: drop_stamp_indexes,
+    },
+];
+
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |migration| migration.version)
+}
+
+pub fn schema_version(conn: &Connection) -> Result<u32, String> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| format!("❌ Failed to read the database schema version because [{}]", e))
+}
+
+// Brings the database up to the latest version, after backing it up next to `db_file`.
+// Refuses one written by a newer syncrab rather than guess what its schema means
+pub fn migrate(conn: &mut Connection, db_file: &Path) -> Result<(), String> {
+    let version = schema_version(conn)?;
+    let latest = latest_version();
+
+    if version > latest {
+        return Err(format!(
+            "❌ The database [{}] is at schema version {}, but this syncrab only knows up to version {}. Update syncrab, or restore the backup made before the upgrade",
+            db_file.display(),
+            version,
+            latest
+        ));
+    }
+    if version == latest {
+        return Ok(());
+    }
+
+    // A brand new database has nothing to lose
+    let is_empty: bool = conn
+        .query_row("SELECT COUNT(*) = 0 FROM sqlite_master", [], |row| {
+            row.get(0)
+        })
+        .map_err(|e| format!("❌ Failed to inspect the database because [{}]", e))?;
+    if !is_empty {
+        let backup = backup(conn, db_file, version)?;
+        println!(
+            "💾 Backed the database up to [{}] before upgrading it",
+            backup.display()
+        );
+    }
+
+    for migration in MIGRATIONS.iter().filter(|migration| migration.version > version) {
+        apply(conn, migration)?;
+    }
+
+    Ok(())
+}
+
+fn apply(conn: &mut Connection, migration: &Migration) -> Result<(), String> {
+    let failed = |e: rusqlite::Error| {
+        format!(
+            "❌ Failed to upgrade the database to version {} ({}) because [{}]",
+            migration.version, migration.description, e
+        )
+    };
+
+    // Immediate: another syncrab binary upgrading at the same time waits, then skips the step
+    let transaction = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(failed)?;
+
+    if schema_version(&transaction)? >= migration.version {
+        return Ok(());
+    }
+
+    (migration.apply)(&transaction).map_err(failed)?;
+    transaction
+        .pragma_update(None, "user_version", migration.version)
+        .map_err(failed)?;
+    transaction.commit().map_err(failed)?;
+
+    println!(
+        "🛠️ Upgraded the database to version {} ({})",
+        migration.version, migration.description
+    );
+    Ok(())
+}
+
+// A consistent copy, even while another binary writes to it
+fn backup(conn: &Connection, db_file: &Path, version: u32) -> Result<PathBuf, String> {
+    let backup = PathBuf::from(format!(
+        "{}.v{}.{}.bak",
+        db_file.display(),
+        version,
+        Local::now().format(DB_BACKUP_FORMAT)
+    ));
+
+    conn.execute("VACUUM INTO ?1", (backup.to_string_lossy(),))
+        .map_err(|e| {
+            format!(
+                "❌ Failed to back the database up to [{}] before upgrading it because [{}]",
+                backup.display(),
+                e
+            )
+        })?;
+
+    Ok(backup)
+}
+
+fn add_column_if_missing(
+    transaction: &Transaction,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> rusqlite::Result<()> {
+    let exists: bool = transaction.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+        (table, column),
+        |row| row.get(0),
+    )?;
+
+    if !exists {
+        transaction.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
+// Steps ─────────────────────────────────────────────────────────
+
+// 1: the schema before versioning. Databases created back then may miss the later columns
+fn create_tables(transaction: &Transaction) -> rusqlite::Result<()> {
+    transaction.execute_batch(
+        "CREATE TABLE IF NOT EXISTS jobs (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+            frequency   TEXT NOT NULL,
+            hour        NUMERIC,
+            minute      INTEGER DEFAULT 0,
+            day         TEXT,
+            source      TEXT NOT NULL,
+            target      TEXT NOT NULL,
+            mirror      INTEGER DEFAULT 1,
+            active      INTEGER DEFAULT 0,
+            retry_max   INTEGER DEFAULT 0,
+            retry_delay INTEGER DEFAULT 5,
+            retry_backoff REAL DEFAULT 2,
+            timeout     INTEGER DEFAULT 0,
+            target_guard TEXT DEFAULT 'none',
+            mount_trigger TEXT,
+            once_per    TEXT DEFAULT 'mount',
+            watch_backend TEXT DEFAULT 'native',
+            poll_interval INTEGER DEFAULT 30
+        );
+
+        CREATE TABLE IF NOT EXISTS logs (
+            id                      INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+            startstamp              TEXT NOT NULL,
+            endstamp                TEXT NOT NULL,
+            status                  TEXT NOT NULL,
+            success_count           INTEGER NOT NULL,
+            failed_count            INTEGER NOT NULL,
+            retry_of                INTEGER,
+            attempt                 INTEGER DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS log_results (
+            log_id           INTEGER NOT NULL,
+            job_id           INTEGER,
+            frequency        TEXT NOT NULL,
+            message          TEXT NOT NULL,
+            source           TEXT NOT NULL,
+            target           TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS pending_runs (
+            id               INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+            job_id           INTEGER NOT NULL,
+            due_at           TEXT NOT NULL,
+            attempt          INTEGER NOT NULL,
+            origin_log_id    INTEGER,
+            reason           TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS schedule_policies (
+            id               INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+            kind             TEXT NOT NULL,
+            day              TEXT,
+            start            TEXT,
+            end              TEXT,
+            until            TEXT
+        );
+
+        -- The mount an on-mount job last saw, cleared while it is unplugged
+        CREATE TABLE IF NOT EXISTS mount_states (
+            job_id           INTEGER PRIMARY KEY NOT NULL,
+            mount_id         INTEGER
+        );
+
+        -- A single row, rewritten by syncrab_w while it runs
+        CREATE TABLE IF NOT EXISTS watcher_heartbeat (
+            id               INTEGER PRIMARY KEY CHECK (id = 1),
+            pid              INTEGER NOT NULL,
+            started_at       TEXT NOT NULL,
+            beat_at          TEXT NOT NULL,
+            watched_count    INTEGER NOT NULL,
+            last_event_at    TEXT
+        );
+
+        -- Whether each active real-time job is being watched
+        CREATE TABLE IF NOT EXISTS watch_states (
+            job_id           INTEGER PRIMARY KEY NOT NULL,
+            state            TEXT NOT NULL,
+            error            TEXT
+        );",
+    )?;
+
+    add_column_if_missing(transaction, "jobs", "mirror", "INTEGER DEFAULT 1")?;
+    add_column_if_missing(transaction, "jobs", "minute", "INTEGER DEFAULT 0")?;
+    add_column_if_missing(transaction, "jobs", "retry_max", "INTEGER DEFAULT 0")?;
+    add_column_if_missing(transaction, "jobs", "retry_delay", "INTEGER DEFAULT 5")?;
+    add_column_if_missing(transaction, "jobs", "retry_backoff", "REAL DEFAULT 2")?;
+    add_column_if_missing(transaction, "jobs", "timeout", "INTEGER DEFAULT 0")?;
+    add_column_if_missing(transaction, "jobs", "target_guard", "TEXT DEFAULT 'none'")?;
+    add_column_if_missing(transaction, "jobs", "mount_trigger", "TEXT")?;
+    add_column_if_missing(transaction, "jobs", "once_per", "TEXT DEFAULT 'mount'")?;
+    add_column_if_missing(transaction, "jobs", "watch_backend", "TEXT DEFAULT 'native'")?;
+    add_column_if_missing(transaction, "jobs", "poll_interval", "INTEGER DEFAULT 30")?;
+    add_column_if_missing(transaction, "logs", "retry_of", "INTEGER")?;
+    add_column_if_missing(transaction, "logs", "attempt", "INTEGER DEFAULT 0")?;
+    add_column_if_missing(transaction, "log_results", "job_id", "INTEGER")?;
+
+    Ok(())
+}
+
+// 2: a deleted log takes its results along, and the journal is looked up by index.
+// SQLite cannot add a foreign key to a table: it is rebuilt, in the same order
+fn link_log_results(transaction: &Transaction) -> rusqlite::Result<()> {
+    transaction.execute_batch(
+        "DELETE FROM log_results WHERE log_id NOT IN (SELECT id FROM logs);
+
+        CREATE TABLE log_results_linked (
+            log_id           INTEGER NOT NULL REFERENCES logs(id) ON DELETE CASCADE,
+            job_id           INTEGER,
+            frequency        TEXT NOT NULL,
+            message          TEXT NOT NULL,
+            source           TEXT NOT NULL,
+            target           TEXT NOT NULL
+        );
+
+        INSERT INTO log_results_linked (rowid, log_id, job_id, frequency, message, source, target)
+            SELECT rowid, log_id, job_id, frequency, message, source, target
+            FROM log_results ORDER BY rowid;
+
+        DROP TABLE log_results;
+        ALTER TABLE log_results_linked RENAME TO log_results;
+
+        CREATE INDEX idx_log_results_log_id ON log_results(log_id);
+        CREATE INDEX idx_log_results_job_id ON log_results(job_id);
+        CREATE INDEX idx_logs_startstamp ON logs(startstamp);
+        CREATE INDEX idx_logs_endstamp ON logs(endstamp);",
+    )
+}
+
+// 3: a single row, so the runners prune the journal every PRUNE_INTERVAL_HOURS between them
+fn create_maintenance(transaction: &Transaction) -> rusqlite::Result<()> {
+    transaction.execute_batch(
+        "CREATE TABLE maintenance (
+            id               INTEGER PRIMARY KEY CHECK (id = 1),
+            pruned_at        TEXT
+        );",
+    )
+}
+
+// 4: mount ids are reused, so a drive unplugged & plugged back in between two runs may look
+// like the same mount. Rows from before keep their mount id until the drive is next seen
+fn key_mount_states(transaction: &Transaction) -> rusqlite::Result<()> {
+    transaction.execute_batch("ALTER TABLE mount_states ADD COLUMN mount_key TEXT;")
+}
+
+// 5: the stamps read "%d-%m-%Y %H:%M", which does not sort by date, so no range, ORDER BY or
+// LIKE on them could use the indexes step 2 added
+fn drop_stamp_indexes(transaction: &Transaction) -> rusqlite::Result<()> {
+    transaction.execute_batch(
+        "DROP INDEX IF EXISTS idx_logs_startstamp;
+        DROP INDEX IF EXISTS idx_logs_endstamp;",
+    )
+}
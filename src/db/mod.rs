@@ -1 +1,3 @@
-pub mod db;
\ No newline at end of file
+pub mod db;
+pub mod migrations;
+pub mod query;
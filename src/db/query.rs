@@ -0,0 +1,248 @@
+// Crates ────────────────────────────────────────────────────────
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use rusqlite::types::Value;
+
+// mods ──────────────────────────────────────────────────────────
+use crate::{
+    consts::{ACTIVE, ALL, DAILY, INACTIVE, WEEKLY},
+    structs::Job,
+};
+
+// JobQuery ──────────────────────────────────────────────────────
+
+// When the scheduled run fires: daily jobs set to that time, weekly ones also set to that day
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleSlot {
+    pub hour: u8,
+    pub minute: u8,
+    pub day: String,
+}
+
+impl ScheduleSlot {
+    pub fn at(now: &NaiveDateTime) -> Self {
+        Self {
+            hour: now.hour() as u8,
+            minute: now.minute() as u8,
+            day: now.weekday().to_string(),
+        }
+    }
+}
+
+// Which jobs to pick, every criterion must hold. Empty/None ones match any job.
+// Values only ever reach the SQL as parameters
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JobQuery {
+    pub frequencies: Vec<String>,
+    pub active: Option<bool>,
+    pub slot: Option<ScheduleSlot>,
+    // Some(empty) matches none
+    pub ids: Option<Vec<u16>>,
+    // In the source, the target or the id. Only ASCII letters ignore case, as in SQLite's lower()
+    pub search: Option<String>,
+}
+
+impl JobQuery {
+    // syncrab_b's arguments: a frequency (or `all`), then optionally `active` or `inactive`
+    pub fn from_args(frequency: &str, state: Option<&str>) -> Self {
+        Self {
+            frequencies: match frequency {
+                ALL => Vec::new(),
+                frequency => vec![frequency.to_string()],
+            },
+            active: match state {
+                Some(ACTIVE) => Some(true),
+                Some(INACTIVE) => Some(false),
+                _ => None,
+            },
+            ..Default::default()
+        }
+    }
+
+    // The jobs the scheduled run picks at `now`
+    pub fn scheduled(now: &NaiveDateTime) -> Self {
+        Self {
+            active: Some(true),
+            slot: Some(ScheduleSlot::at(now)),
+            ..Default::default()
+        }
+    }
+
+    pub fn ids(ids: &[u16]) -> Self {
+        Self {
+            ids: Some(ids.to_vec()),
+            ..Default::default()
+        }
+    }
+
+    // The WHERE clause, its placeholders numbered from ?1, and the values they take
+    pub fn to_sql(&self) -> (String, Vec<Value>) {
+        let mut conditions: Vec<String> = Vec::new();
+        let mut params: Vec<Value> = Vec::new();
+        let mut bind = |value: Value| {
+            params.push(value);
+            format!("?{}", params.len())
+        };
+
+        if !self.frequencies.is_empty() {
+            let placeholders: Vec<String> = self
+                .frequencies
+                .iter()
+                .map(|frequency| bind(Value::Text(frequency.clone())))
+                .collect();
+            conditions.push(format!("frequency IN ({})", placeholders.join(", ")));
+        }
+
+        if let Some(active) = self.active {
+            conditions.push(format!("active = {}", bind(Value::Integer(active as i64))));
+        }
+
+        if let Some(slot) = &self.slot {
+            conditions.push(format!(
+                "hour = {} AND minute = {} AND (frequency = {} OR (frequency = {} AND instr(',' || day || ',', ',' || {} || ',') > 0))",
+                bind(Value::Integer(slot.hour as i64)),
+                bind(Value::Integer(slot.minute as i64)),
+                bind(Value::Text(DAILY.into())),
+                bind(Value::Text(WEEKLY.into())),
+                bind(Value::Text(slot.day.clone())),
+            ));
+        }
+
+        match &self.ids {
+            Some(ids) if ids.is_empty() => conditions.push("0".into()),
+            Some(ids) => {
+                let placeholders: Vec<String> = ids
+                    .iter()
+                    .map(|id| bind(Value::Integer(*id as i64)))
+                    .collect();
+                conditions.push(format!("id IN ({})", placeholders.join(", ")));
+            }
+            None => {}
+        }
+
+        if let Some(search) = self.search.as_ref().filter(|search| !search.is_empty()) {
+            let term = bind(Value::Text(search.to_ascii_lowercase()));
+            conditions.push(format!(
+                "(instr(lower(source), {term}) > 0 OR instr(lower(target), {term}) > 0 OR instr(CAST(id AS TEXT), {term}) > 0)"
+            ));
+        }
+
+        let clause = if conditions.is_empty() {
+            "1".to_string()
+        } else {
+            conditions.join(" AND ")
+        };
+        (clause, params)
+    }
+
+    // The same criteria, on jobs already loaded (e.g. the TUI's search as it is typed)
+    pub fn matches(&self, job: &Job) -> bool {
+        let search = self
+            .search
+            .as_ref()
+            .map(|search| search.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        (self.frequencies.is_empty() || self.frequencies.contains(&job.frequency))
+            && self.active.is_none_or(|active| (job.active == 1) == active)
+            && self.slot.as_ref().is_none_or(|slot| {
+                job.hour == slot.hour
+                    && job.minute == slot.minute
+                    && (job.frequency == DAILY
+                        || (job.frequency == WEEKLY && job.days.contains(&slot.day)))
+            })
+            && self
+                .ids
+                .as_ref()
+                .is_none_or(|ids| job.id.is_some_and(|id| ids.contains(&id)))
+            && (search.is_empty()
+                || job.source.to_ascii_lowercase().contains(&search)
+                || job.target.to_ascii_lowercase().contains(&search)
+                || job.id.is_some_and(|id| id.to_string().contains(&search)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use chrono::NaiveDate;
+    use rusqlite::Connection;
+
+    use super::*;
+    use crate::{
+        consts::REAL_TIME,
+        db::{
+            db::{find_jobs, insert},
+            migrations::migrate,
+        },
+    };
+
+    fn job(frequency: &str, source: &str, active: u8, hour: u8, days: &[&str]) -> Job {
+        let mut job = Job::new(frequency);
+        job.source = source.to_string();
+        job.target = format!("/media/Backup/{}", source.trim_start_matches('/'));
+        job.active = active;
+        job.hour = hour;
+        job.days = days.iter().map(|day| day.to_string()).collect();
+        job
+    }
+
+    // find_jobs (the SQL) and matches (in memory) must pick the same jobs
+    #[test]
+    fn to_sql_agrees_with_matches() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn, Path::new(":memory:")).unwrap();
+
+        for job in [
+            job(DAILY, "/home/Photos", 1, 9, &[]),
+            job(DAILY, "/home/Été/Clichés", 0, 9, &[]),
+            job(WEEKLY, "/home/étude", 1, 9, &["Mon", "Wed"]),
+            job(WEEKLY, "/srv/ÉTUDE", 1, 9, &["Tue"]),
+            job(REAL_TIME, "/home/docs", 1, 0, &[]),
+        ] {
+            insert(&mut conn, &job).unwrap();
+        }
+        let jobs = find_jobs(&conn, &JobQuery::default());
+        assert_eq!(jobs.len(), 5);
+
+        let wednesday = NaiveDate::from_ymd_opt(2026, 10, 14)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let search = |term: &str| JobQuery {
+            search: Some(term.to_string()),
+            ..Default::default()
+        };
+        let queries = [
+            JobQuery::default(),
+            JobQuery::from_args(WEEKLY, Some(ACTIVE)),
+            JobQuery::from_args(ALL, Some(INACTIVE)),
+            JobQuery::scheduled(&wednesday),
+            JobQuery::ids(&[1, 3, 42]),
+            JobQuery::ids(&[]),
+            search("PHOTOS"),
+            search("été"),
+            search("Été"),
+            search("ÉTUDE"),
+            search("backup"),
+            search("3"),
+            JobQuery {
+                frequencies: vec![DAILY.into(), WEEKLY.into()],
+                active: Some(true),
+                search: Some("HOME".into()),
+                ..Default::default()
+            },
+        ];
+
+        for query in queries {
+            let in_sql: Vec<Option<u16>> =
+                find_jobs(&conn, &query).iter().map(|job| job.id).collect();
+            let in_memory: Vec<Option<u16>> = jobs
+                .iter()
+                .filter(|job| query.matches(job))
+                .map(|job| job.id)
+                .collect();
+            assert_eq!(in_sql, in_memory, "{:?}", query);
+        }
+    }
+}
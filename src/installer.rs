@@ -0,0 +1,445 @@
+// Standards ─────────────────────────────────────────────────────
+use std::{
+    env, fmt,
+    fs::{create_dir_all, remove_file, write},
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    str::FromStr,
+};
+
+// mods ──────────────────────────────────────────────────────────
+use crate::{
+    consts::{
+        BACKUP_BIN, BACKUP_SERVICE, BACKUP_TIMER, CRON, CRON_MARKER, SYSTEMD, WATCHER_BIN,
+        WATCHER_SERVICE,
+    },
+    db::db::{db_path, init_db},
+    utils::capitalise,
+};
+
+// Scheduler ─────────────────────────────────────────────────────
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Scheduler {
+    Cron,
+    Systemd,
+}
+
+impl Scheduler {
+    // Prefer systemd user units whenever a user manager is reachable
+    pub fn detect() -> Self {
+        let state = output_of("systemctl", &["--user", "is-system-running"]);
+        match state.as_str() {
+            "running" | "degraded" | "starting" | "initializing" => Scheduler::Systemd,
+            _ => Scheduler::Cron,
+        }
+    }
+}
+
+impl fmt::Display for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scheduler::Cron => write!(f, "{}", CRON),
+            Scheduler::Systemd => write!(f, "{}", SYSTEMD),
+        }
+    }
+}
+
+impl FromStr for Scheduler {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            CRON => Ok(Scheduler::Cron),
+            SYSTEMD => Ok(Scheduler::Systemd),
+            _ => Err(format!(
+                "Could not parse the value [{}] to the enum Scheduler",
+                s.trim()
+            )),
+        }
+    }
+}
+
+// Paths ─────────────────────────────────────────────────────────
+struct Paths {
+    backup_bin: PathBuf,
+    watcher_bin: PathBuf,
+    work_dir: PathBuf,
+}
+
+impl Paths {
+    fn resolve() -> Result<Self, String> {
+        let exe_path = env::current_exe()
+            .map_err(|e| format!("❌ Failed to get current executable path because [{}]", e))?;
+        let exe_dir = exe_path
+            .parent()
+            .ok_or("❌ Executable must be in some directory")?;
+
+        let backup_bin = exe_dir.join(BACKUP_BIN);
+        let watcher_bin = exe_dir.join(WATCHER_BIN);
+
+        for bin in [&backup_bin, &watcher_bin] {
+            if !bin.is_file() {
+                return Err(format!(
+                    "❌ Could not find [{}]. Install all the syncrab binaries side by side first",
+                    bin.display()
+                ));
+            }
+        }
+
+        // The binaries resolve the DB from their working directory in debug builds
+        let work_dir = db_path()
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or("❌ The database must be in some directory")?;
+
+        Ok(Self {
+            backup_bin,
+            watcher_bin,
+            work_dir,
+        })
+    }
+}
+
+// Install ───────────────────────────────────────────────────────
+pub fn install(scheduler: Scheduler, dry_run: bool) -> Result<(), String> {
+    let paths = Paths::resolve()?;
+
+    // Created, or upgraded, before anything runs against it
+    if !dry_run {
+        init_db();
+        println!("✅ The database is ready at [{}]", db_path().display());
+    }
+
+    // Never leave both schedulers running the same binaries
+    let other = match scheduler {
+        Scheduler::Cron => Scheduler::Systemd,
+        Scheduler::Systemd => Scheduler::Cron,
+    };
+    uninstall(Some(other), dry_run)?;
+
+    match scheduler {
+        Scheduler::Cron => {
+            let crontab = without_syncrab_entries(&read_crontab(), Some(&paths))
+                .into_iter()
+                .chain(cron_entries(&paths))
+                .collect::<Vec<String>>()
+                .join("\n")
+                + "\n";
+
+            if dry_run {
+                println!("📝 Would write the crontab:\n{}", crontab);
+                return Ok(());
+            }
+
+            write_crontab(&crontab)?;
+            println!("✅ Installed the syncrab crontab entries");
+        }
+        Scheduler::Systemd => {
+            let units = systemd_units(&paths)?;
+
+            if dry_run {
+                for (path, content) in &units {
+                    println!("📝 Would write [{}]:\n{}", path.display(), content);
+                }
+                println!(
+                    "📝 Would run: systemctl --user enable --now {} {}",
+                    WATCHER_SERVICE, BACKUP_TIMER
+                );
+                return Ok(());
+            }
+
+            for (path, content) in &units {
+                if let Some(parent) = path.parent() {
+                    create_dir_all(parent).map_err(|e| {
+                        format!(
+                            "❌ Could not create directory [{}] because [{}]",
+                            parent.display(),
+                            e
+                        )
+                    })?;
+                }
+                write(path, content).map_err(|e| {
+                    format!("❌ Failed to write [{}] because [{}]", path.display(), e)
+                })?;
+            }
+
+            run("systemctl", &["--user", "daemon-reload"])?;
+            run(
+                "systemctl",
+                &["--user", "enable", "--now", WATCHER_SERVICE, BACKUP_TIMER],
+            )?;
+            println!("✅ Installed and started the syncrab systemd user units");
+        }
+    }
+
+    Ok(())
+}
+
+// Uninstall ─────────────────────────────────────────────────────
+pub fn uninstall(scheduler: Option<Scheduler>, dry_run: bool) -> Result<(), String> {
+    let schedulers = match scheduler {
+        Some(scheduler) => vec![scheduler],
+        None => vec![Scheduler::Cron, Scheduler::Systemd],
+    };
+
+    for scheduler in schedulers {
+        match scheduler {
+            Scheduler::Cron => {
+                let current = read_crontab();
+                let paths = Paths::resolve().ok();
+                let kept = without_syncrab_entries(&current, paths.as_ref());
+
+                if kept.len() == current.lines().count() {
+                    println!("💤 No syncrab crontab entries to remove");
+                    continue;
+                }
+
+                let crontab = kept.join("\n") + "\n";
+                if dry_run {
+                    println!("📝 Would write the crontab:\n{}", crontab);
+                    continue;
+                }
+
+                write_crontab(&crontab)?;
+                println!("✅ Removed the syncrab crontab entries");
+            }
+            Scheduler::Systemd => {
+                let unit_paths: Vec<PathBuf> = [WATCHER_SERVICE, BACKUP_SERVICE, BACKUP_TIMER]
+                    .iter()
+                    .map(|unit| systemd_dir().map(|dir| dir.join(unit)))
+                    .collect::<Result<_, _>>()?;
+
+                if unit_paths.iter().all(|path| !path.exists()) {
+                    println!("💤 No syncrab systemd user units to remove");
+                    continue;
+                }
+
+                if dry_run {
+                    println!(
+                        "📝 Would run: systemctl --user disable --now {} {}",
+                        WATCHER_SERVICE, BACKUP_TIMER
+                    );
+                    for path in unit_paths.iter().filter(|path| path.exists()) {
+                        println!("📝 Would remove [{}]", path.display());
+                    }
+                    continue;
+                }
+
+                // The units may already be stopped, which is fine
+                let _ = run(
+                    "systemctl",
+                    &["--user", "disable", "--now", WATCHER_SERVICE, BACKUP_TIMER],
+                );
+
+                for path in unit_paths.iter().filter(|path| path.exists()) {
+                    remove_file(path).map_err(|e| {
+                        format!("❌ Failed to remove [{}] because [{}]", path.display(), e)
+                    })?;
+                }
+
+                run("systemctl", &["--user", "daemon-reload"])?;
+                println!("✅ Removed the syncrab systemd user units");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Status ────────────────────────────────────────────────────────
+pub fn status() -> Result<(), String> {
+    let paths = Paths::resolve().ok();
+
+    println!("📋 {}", capitalise(&Scheduler::Cron.to_string()));
+    let crontab = read_crontab();
+    let entries: Vec<&str> = crontab
+        .lines()
+        .filter(|line| is_syncrab_entry(line, paths.as_ref()))
+        .collect();
+
+    if entries.is_empty() {
+        println!("   💤 No syncrab entries");
+    } else {
+        for entry in entries {
+            println!("   {}", entry);
+        }
+    }
+
+    println!("📋 {}", capitalise(&Scheduler::Systemd.to_string()));
+    let dir = systemd_dir()?;
+    for unit in [WATCHER_SERVICE, BACKUP_SERVICE, BACKUP_TIMER] {
+        if !dir.join(unit).exists() {
+            println!("   💤 {} is not installed", unit);
+            continue;
+        }
+
+        let enabled = output_of("systemctl", &["--user", "is-enabled", unit]);
+        let active = output_of("systemctl", &["--user", "is-active", unit]);
+        println!("   {} [{}, {}]", unit, enabled, active);
+    }
+
+    Ok(())
+}
+
+// Helpers ───────────────────────────────────────────────────────
+fn cron_entries(paths: &Paths) -> Vec<String> {
+    let work_dir = paths.work_dir.display();
+    vec![
+        format!(
+            "* * * * * cd '{}' && '{}' {}",
+            work_dir,
+            paths.backup_bin.display(),
+            CRON_MARKER
+        ),
+        format!(
+            "@reboot cd '{}' && '{}' {}",
+            work_dir,
+            paths.watcher_bin.display(),
+            CRON_MARKER
+        ),
+    ]
+}
+
+// Also catches the entries written by older install scripts
+fn is_syncrab_entry(line: &str, paths: Option<&Paths>) -> bool {
+    line.ends_with(CRON_MARKER)
+        || paths.is_some_and(|paths| {
+            line.contains(&paths.backup_bin.display().to_string())
+                || line.contains(&paths.watcher_bin.display().to_string())
+        })
+}
+
+fn without_syncrab_entries(crontab: &str, paths: Option<&Paths>) -> Vec<String> {
+    crontab
+        .lines()
+        .filter(|line| !is_syncrab_entry(line, paths))
+        .map(str::to_string)
+        .collect()
+}
+
+fn systemd_dir() -> Result<PathBuf, String> {
+    if let Some(config) = env::var_os("XDG_CONFIG_HOME") {
+        return Ok(PathBuf::from(config).join("systemd/user"));
+    }
+
+    env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".config/systemd/user"))
+        .ok_or("❌ Could not locate the systemd user directory because $HOME is not set".into())
+}
+
+fn systemd_units(paths: &Paths) -> Result<Vec<(PathBuf, String)>, String> {
+    let dir = systemd_dir()?;
+    let work_dir = paths.work_dir.display();
+
+    Ok(vec![
+        (
+            dir.join(WATCHER_SERVICE),
+            format!(
+                "[Unit]
+Description=Syncrab Real-Time File Synchroniser
+
+[Service]
+WorkingDirectory={}
+ExecStart=\"{}\"
+Restart=on-failure
+
+[Install]
+WantedBy=default.target
+",
+                work_dir,
+                paths.watcher_bin.display()
+            ),
+        ),
+        (
+            dir.join(BACKUP_SERVICE),
+            format!(
+                "[Unit]
+Description=Syncrab Backup Batch Performer
+
+[Service]
+Type=oneshot
+WorkingDirectory={}
+ExecStart=\"{}\"
+",
+                work_dir,
+                paths.backup_bin.display()
+            ),
+        ),
+        (
+            dir.join(BACKUP_TIMER),
+            "[Unit]
+Description=Run the Syncrab Backup Batch Performer every minute
+
+[Timer]
+OnCalendar=*-*-* *:*:00
+AccuracySec=1s
+
+[Install]
+WantedBy=timers.target
+"
+            .to_string(),
+        ),
+    ])
+}
+
+// Without cron (or without a crontab yet) there are simply no entries
+fn read_crontab() -> String {
+    match Command::new("crontab").arg("-l").output() {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).to_string()
+        }
+        _ => String::new(),
+    }
+}
+
+fn write_crontab(crontab: &str) -> Result<(), String> {
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("❌ Failed to run crontab because [{}]", e))?;
+
+    if let Some(stdin) = child.stdin.as_mut() {
+        stdin
+            .write_all(crontab.as_bytes())
+            .map_err(|e| format!("❌ Failed to write the crontab because [{}]", e))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("❌ Failed to write the crontab because [{}]", e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("❌ crontab exited with [{}]", status))
+    }
+}
+
+fn run(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("❌ Failed to run {} because [{}]", program, e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "❌ [{} {}] failed because [{}]",
+            program,
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+// Stdout of a command whatever its exit code, as systemctl reports states through it
+fn output_of(program: &str, args: &[&str]) -> String {
+    Command::new(program)
+        .args(args)
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default()
+}
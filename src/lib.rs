@@ -1,5 +1,10 @@
 pub mod app;
+pub mod control;
 pub mod db;
+pub mod installer;
+pub mod lock;
+pub mod policy;
+pub mod retention;
 
 pub mod utils;
 pub mod structs;
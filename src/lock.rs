@@ -0,0 +1,314 @@
+// Standards ─────────────────────────────────────────────────────
+use std::{
+    fs::{File, OpenOptions, TryLockError, create_dir_all, metadata, remove_file},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        Arc, Mutex,
+        mpsc::{RecvTimeoutError, channel},
+    },
+    thread::{sleep, spawn},
+    time::{Duration, Instant, SystemTime},
+};
+
+// Crates ────────────────────────────────────────────────────────
+use chrono::Local;
+
+// mods ──────────────────────────────────────────────────────────
+use crate::{
+    consts::{ABANDON_GRACE_SECS, CANCEL_EXT, CANCELLED, LOCK_POLL_MS, LOCKS_DIR, TIMED_OUT},
+    db::db::db_path,
+};
+
+// LockError ─────────────────────────────────────────────────────
+#[derive(Debug)]
+pub enum LockError {
+    // Another runner holds the job
+    Busy(String),
+    // The lock itself couldn't be taken
+    Failed(String),
+}
+
+// JobLock ───────────────────────────────────────────────────────
+
+// Advisory lock held by whichever runner (syncrab_b or syncrab_w) is syncing a job.
+// The kernel releases it when the holder dies, so a crashed runner never blocks a job,
+// it only leaves its pid behind in the lock file, which is how stale locks are spotted.
+#[derive(Debug)]
+pub struct JobLock {
+    file: File,
+    pub path: PathBuf,
+    pub stale_holder: Option<String>,
+    pub acquired_at: SystemTime,
+}
+
+impl JobLock {
+    // `wait`: None waits for as long as it takes, Some(duration) gives up after it
+    pub fn acquire(job_id: u16, wait: Option<Duration>) -> Result<Self, LockError> {
+        let path = lock_path(job_id).map_err(LockError::Failed)?;
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| {
+                LockError::Failed(format!(
+                    "Could not open the lock file [{}] because [{}]",
+                    path.display(),
+                    e
+                ))
+            })?;
+
+        let deadline = wait.map(|wait| Instant::now() + wait);
+
+        loop {
+            match file.try_lock() {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) => {
+                    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                        return Err(LockError::Busy(format!(
+                            "job [{}] is already running ({})",
+                            job_id,
+                            read_holder(&mut file).unwrap_or_else(|| "unknown holder".into())
+                        )));
+                    }
+                    sleep(Duration::from_millis(LOCK_POLL_MS));
+                }
+                Err(TryLockError::Error(e)) => {
+                    return Err(LockError::Failed(format!(
+                        "Could not lock [{}] because [{}]",
+                        path.display(),
+                        e
+                    )));
+                }
+            }
+        }
+
+        let acquired_at = SystemTime::now();
+
+        // A holder left in a lock we could take belongs to a runner that crashed
+        let stale_holder = read_holder(&mut file);
+
+        let holder = format!(
+            "pid {} since {}",
+            process::id(),
+            Local::now().format("%d-%m-%Y %H:%M:%S")
+        );
+        file.set_len(0)
+            .and_then(|_| file.seek(SeekFrom::Start(0)))
+            .and_then(|_| file.write_all(holder.as_bytes()))
+            .map_err(|e| {
+                LockError::Failed(format!(
+                    "Could not write the lock file [{}] because [{}]",
+                    path.display(),
+                    e
+                ))
+            })?;
+
+        Ok(Self {
+            file,
+            path,
+            stale_holder,
+            acquired_at,
+        })
+    }
+}
+
+impl Drop for JobLock {
+    fn drop(&mut self) {
+        // Clear the holder so the next runner doesn't mistake a clean exit for a crash.
+        // The file itself stays: deleting it would let two runners lock different inodes
+        let _ = self.file.set_len(0);
+        let _ = self.file.unlock();
+    }
+}
+
+// Interrupt ─────────────────────────────────────────────────────
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interrupt {
+    Cancelled,
+    TimedOut,
+}
+
+impl Interrupt {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Interrupt::Cancelled => CANCELLED,
+            Interrupt::TimedOut => TIMED_OUT,
+        }
+    }
+}
+
+// CancelToken ───────────────────────────────────────────────────
+
+// Checked by copy_dir between files. A run is cancelled by dropping a marker file next to
+// the job's lock (so any process can ask for it), and times out once its deadline passes.
+// It owns the job's lock: whoever still copies with it, even an abandoned copy, keeps the job
+#[derive(Debug)]
+pub struct CancelToken {
+    marker: Option<PathBuf>,
+    deadline: Option<Instant>,
+    interrupt: Mutex<Option<Interrupt>>,
+    // Released after the marker is cleared, see Drop
+    _lock: JobLock,
+}
+
+impl CancelToken {
+    // `timeout`: None lets the run take as long as it needs
+    pub fn new(job_id: u16, lock: JobLock, timeout: Option<Duration>) -> Self {
+        let marker = cancel_path(job_id).ok();
+
+        // A request left behind for a run that already ended must not cancel this one, but
+        // one made since the lock was taken is for this run
+        if let Some(marker) = &marker {
+            let is_stale = metadata(marker)
+                .and_then(|marker| marker.modified())
+                .is_ok_and(|requested_at| requested_at < lock.acquired_at);
+            if is_stale {
+                let _ = remove_file(marker);
+            }
+        }
+
+        Self {
+            marker,
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+            interrupt: Mutex::new(None),
+            _lock: lock,
+        }
+    }
+
+    pub fn check(&self) -> Option<Interrupt> {
+        let mut interrupt = self.interrupt.lock().unwrap();
+        if interrupt.is_none() {
+            if self.marker.as_ref().is_some_and(|marker| marker.exists()) {
+                *interrupt = Some(Interrupt::Cancelled);
+            } else if self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                *interrupt = Some(Interrupt::TimedOut);
+            }
+        }
+
+        *interrupt
+    }
+
+    // What stopped the run, if anything did
+    pub fn interrupt(&self) -> Option<Interrupt> {
+        *self.interrupt.lock().unwrap()
+    }
+}
+
+// Runs `work` on its own thread while the caller keeps checking `cancel`: a copy stuck on a
+// dead network mount never returns, so it only gets ABANDON_GRACE_SECS once the run is
+// interrupted, then it is left behind. Its share of `cancel` keeps the job locked until it ends
+pub fn run_interruptible<T: Send + 'static>(
+    cancel: &Arc<CancelToken>,
+    work: impl FnOnce(&CancelToken) -> T + Send + 'static,
+) -> Result<T, String> {
+    let (tx, rx) = channel();
+    let token = Arc::clone(cancel);
+    spawn(move || {
+        let _ = tx.send(work(&token));
+    });
+
+    let grace = Duration::from_secs(ABANDON_GRACE_SECS);
+    let mut interrupted_at: Option<Instant> = None;
+    loop {
+        match rx.recv_timeout(Duration::from_millis(LOCK_POLL_MS)) {
+            Ok(result) => return Ok(result),
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err("The copy stopped unexpectedly".into());
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        if let Some(interrupt) = cancel.check()
+            && interrupted_at.get_or_insert_with(Instant::now).elapsed() >= grace
+        {
+            return Err(format!(
+                "Run {} but its copy is stuck on a file: abandoned after {}s, still running and holding the job",
+                interrupt.to_str(),
+                ABANDON_GRACE_SECS
+            ));
+        }
+    }
+}
+
+impl Drop for CancelToken {
+    fn drop(&mut self) {
+        if let Some(marker) = &self.marker {
+            let _ = remove_file(marker);
+        }
+    }
+}
+
+// Asks the runner currently syncing the job to stop after the file it is copying
+pub fn request_cancel(job_id: u16) -> Result<(), String> {
+    match JobLock::acquire(job_id, Some(Duration::ZERO)) {
+        Ok(_) => return Err(format!("❌ Job [{}] is not running", job_id)),
+        Err(LockError::Busy(_)) => {}
+        Err(LockError::Failed(error)) => return Err(format!("❌ {}", error)),
+    }
+
+    let path = cancel_path(job_id)?;
+    File::create(&path).map_err(|e| {
+        format!(
+            "❌ Could not create the cancel request [{}] because [{}]",
+            path.display(),
+            e
+        )
+    })?;
+
+    Ok(())
+}
+
+// Helpers ───────────────────────────────────────────────────────
+pub fn lock_path(job_id: u16) -> Result<PathBuf, String> {
+    Ok(locks_dir()?.join(format!("job_{}.lock", job_id)))
+}
+
+pub fn cancel_path(job_id: u16) -> Result<PathBuf, String> {
+    Ok(locks_dir()?.join(format!("job_{}.{}", job_id, CANCEL_EXT)))
+}
+
+fn locks_dir() -> Result<PathBuf, String> {
+    let dir = db_path()
+        .parent()
+        .map(|dir| dir.join(LOCKS_DIR))
+        .ok_or("The database must be in some directory")?;
+
+    create_dir_all(&dir).map_err(|e| {
+        format!(
+            "Could not create the locks directory [{}] because [{}]",
+            dir.display(),
+            e
+        )
+    })?;
+
+    Ok(dir)
+}
+
+fn read_holder(file: &mut File) -> Option<String> {
+    let mut holder = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut holder).ok()?;
+
+    let holder = holder.trim();
+    if holder.is_empty() {
+        None
+    } else if is_holder_alive(holder) {
+        Some(holder.to_string())
+    } else {
+        Some(format!("{} (process is gone)", holder))
+    }
+}
+
+fn is_holder_alive(holder: &str) -> bool {
+    holder
+        .strip_prefix("pid ")
+        .and_then(|rest| rest.split_whitespace().next())
+        .is_some_and(|pid| Path::new("/proc").join(pid).exists())
+}
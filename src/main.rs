@@ -1,5 +1,5 @@
 // Standards ─────────────────────────────────────────────────────
-use std::collections::HashMap;
+use std::{collections::HashMap, env, process};
 
 // Crates ────────────────────────────────────────────────────────
 use color_eyre::Result;
@@ -7,21 +7,36 @@ use color_eyre::Result;
 // mods ──────────────────────────────────────────────────────────
 use syncrab::app::app::App;
 use syncrab::app::tui;
-use syncrab::db::db::{get_all_jobs, get_logs, init_db};
-use syncrab::structs::{Job, Log, Stat};
+use syncrab::consts::{
+    ADD, BLACKOUT, CANCEL, CRON, DRY_RUN, INSTALL, PAUSE, PRUNE, REMOVE, RESUME, STATUS, SYSTEMD,
+    UNINSTALL,
+};
+use syncrab::db::db::{get_all_jobs, get_logs, get_policies, init_db};
+use syncrab::installer::{Scheduler, install, status, uninstall};
+use syncrab::lock::request_cancel;
+use syncrab::policy::{add_blackout, list_policies, pause, remove_blackout, resume};
+use syncrab::retention::prune;
+use syncrab::structs::{Job, Log, SchedulePolicy, Stat};
 use syncrab::utils::get_stats;
 
 // Init ──────────────────────────────────────────────────────────
 fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        run_command(&args);
+        return Ok(());
+    }
+
     let db = init_db();
     let jobs: HashMap<&'static str, Vec<Job>> = get_all_jobs(&db);
     let stats: HashMap<&'static str, Stat> = get_stats(&jobs);
     let logs: Vec<Log> = get_logs(&db);
+    let policies: Vec<SchedulePolicy> = get_policies(&db);
 
     color_eyre::install()?;
     let mut terminal = tui::init()?;
     let mut app = App::new(db);
-    let result = app.run(&mut terminal, jobs, logs, stats);
+    let result = app.run(&mut terminal, jobs, logs, stats, policies);
     if let Err(err) = tui::restore() {
         eprintln!(
             "failed to restore terminal. Run `reset` or restart your terminal to recover: {err}"
@@ -30,3 +45,96 @@ fn main() -> Result<()> {
     Ok(result?)
 }
 
+// Commands ──────────────────────────────────────────────────────
+fn run_command(args: &[String]) {
+    let command = args[0].to_lowercase();
+
+    if command == CANCEL {
+        return cancel_command(args);
+    }
+
+    if [PAUSE, RESUME, BLACKOUT].contains(&command.as_str()) {
+        return policy_command(&command, &args[1..]);
+    }
+
+    let dry_run = args.iter().any(|arg| arg == DRY_RUN);
+    let scheduler = match args
+        .iter()
+        .skip(1)
+        .find(|arg| arg.as_str() != DRY_RUN)
+        .map(|arg| arg.to_lowercase().parse::<Scheduler>())
+        .transpose()
+    {
+        Ok(scheduler) => scheduler,
+        Err(_) => exit_with_usage(),
+    };
+
+    let result = match command.as_str() {
+        PRUNE if scheduler.is_none() => prune(dry_run),
+        INSTALL => install(scheduler.unwrap_or_else(Scheduler::detect), dry_run),
+        UNINSTALL => uninstall(scheduler, dry_run),
+        STATUS => status(),
+        _ => exit_with_usage(),
+    };
+
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn cancel_command(args: &[String]) {
+    let Some(job_id) = args.get(1).and_then(|arg| arg.parse::<u16>().ok()) else {
+        exit_with_usage();
+    };
+
+    match request_cancel(job_id) {
+        Ok(_) => println!("⛔ Asked job [{}] to stop after its current file", job_id),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+fn policy_command(command: &str, args: &[String]) {
+    let result = match (command, args) {
+        (PAUSE, []) => pause(None),
+        (PAUSE, until) => pause(Some(&until.join(" "))),
+        (RESUME, []) => resume(),
+        (BLACKOUT, []) => list_policies(),
+        (BLACKOUT, [action, days, window]) if action == ADD => add_blackout(days, window),
+        (BLACKOUT, [action, id]) if action == REMOVE => match id.parse::<u16>() {
+            Ok(id) => remove_blackout(id),
+            Err(_) => exit_with_usage(),
+        },
+        _ => exit_with_usage(),
+    };
+
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!(
+        "❌ Usage: syncrab [{} | {}] [Optional: {}, {}] [Optional: {}] or syncrab {} or syncrab {} <job id>\n\
+         or syncrab {} [Optional: {}] or syncrab {} [Optional: HH:MM | YYYY-MM-DD HH:MM] or syncrab {} or syncrab {} [Optional: {} <days> <HH:MM-HH:MM> | {} <id>]",
+        INSTALL,
+        UNINSTALL,
+        CRON,
+        SYSTEMD,
+        DRY_RUN,
+        STATUS,
+        CANCEL,
+        PRUNE,
+        DRY_RUN,
+        PAUSE,
+        RESUME,
+        BLACKOUT,
+        ADD,
+        REMOVE
+    );
+    process::exit(1);
+}
@@ -0,0 +1,141 @@
+// Crates ────────────────────────────────────────────────────────
+use chrono::{Duration, Local, NaiveDateTime, NaiveTime};
+
+// mods ──────────────────────────────────────────────────────────
+use crate::{
+    consts::{BLACKOUT, DUE_FORMAT, PAUSE, WINDOW_SEPARATOR},
+    db::db::{delete_policies_of_kind, delete_policy, get_policies, init_db, insert_policy},
+    structs::{Job, SchedulePolicy},
+    utils::{blocked_until, parse_time},
+};
+
+// Pause ─────────────────────────────────────────────────────────
+
+// `until`: "YYYY-MM-DD HH:MM", or "HH:MM" for its next occurrence. Defaults to tomorrow 00:00
+pub fn pause(until: Option<&str>) -> Result<(), String> {
+    let now = Local::now().naive_local();
+    let until = match until {
+        Some(until) => parse_until(until, &now)?,
+        None => (now.date() + Duration::days(1)).and_time(NaiveTime::MIN),
+    };
+
+    if until <= now {
+        return Err(format!(
+            "❌ Could not pause because [{}] is already past",
+            until.format(DUE_FORMAT)
+        ));
+    }
+
+    // A single global pause: a new one replaces the old one
+    let mut conn = init_db();
+    delete_policies_of_kind(&mut conn, PAUSE)?;
+    insert_policy(&mut conn, &SchedulePolicy::new_pause(&until))?;
+
+    println!(
+        "⏸️ Scheduled backups are paused until {}",
+        until.format(DUE_FORMAT)
+    );
+    Ok(())
+}
+
+pub fn resume() -> Result<(), String> {
+    let mut conn = init_db();
+    match delete_policies_of_kind(&mut conn, PAUSE)? {
+        0 => println!("💤 Scheduled backups were not paused"),
+        _ => println!("▶️ Scheduled backups are resumed"),
+    }
+    Ok(())
+}
+
+// Blackout ──────────────────────────────────────────────────────
+
+// `days`: comma separated week days, `window`: "HH:MM-HH:MM" (may run past midnight)
+pub fn add_blackout(days: &str, window: &str) -> Result<(), String> {
+    let week_days = Job::parse_days(Some(days.to_string()));
+    if week_days.is_empty() {
+        return Err(format!(
+            "❌ Could not add the blackout because [{}] holds no week day",
+            days
+        ));
+    }
+
+    let (start, end) = window
+        .split_once(WINDOW_SEPARATOR)
+        .and_then(|(start, end)| Some((parse_time(start)?, parse_time(end)?)))
+        .filter(|(start, end)| start != end)
+        .ok_or_else(|| {
+            format!(
+                "❌ Could not add the blackout because [{}] is not a HH:MM{}HH:MM window",
+                window, WINDOW_SEPARATOR
+            )
+        })?;
+
+    let policy = SchedulePolicy::new_blackout(week_days, start, end);
+    let mut conn = init_db();
+    let id = insert_policy(&mut conn, &policy)?;
+
+    println!("🚫 Blackout [{}] added: {}", id, policy.describe());
+    Ok(())
+}
+
+pub fn remove_blackout(id: u16) -> Result<(), String> {
+    let mut conn = init_db();
+    let is_blackout = get_policies(&conn)
+        .iter()
+        .any(|policy| policy.id == Some(id) && policy.kind == BLACKOUT);
+
+    if !is_blackout {
+        return Err(format!("❌ There is no blackout [{}]", id));
+    }
+
+    delete_policy(&mut conn, id)?;
+    println!("🗑️ Blackout [{}] removed", id);
+    Ok(())
+}
+
+pub fn list_policies() -> Result<(), String> {
+    let conn = init_db();
+    let policies = get_policies(&conn);
+    let now = Local::now().naive_local();
+
+    match blocked_until(&policies, &now) {
+        Some(until) => println!(
+            "⏸️ Scheduled backups are blocked until {}",
+            until.format(DUE_FORMAT)
+        ),
+        None => println!("▶️ Scheduled backups are running"),
+    }
+
+    if policies.is_empty() {
+        println!("   💤 No schedule policies");
+    }
+
+    for policy in &policies {
+        println!(
+            "   [{}] {}",
+            policy.id.unwrap_or_default(),
+            policy.describe()
+        );
+    }
+
+    Ok(())
+}
+
+// Helpers ───────────────────────────────────────────────────────
+fn parse_until(until: &str, now: &NaiveDateTime) -> Result<NaiveDateTime, String> {
+    if let Ok(until) = NaiveDateTime::parse_from_str(until.trim(), DUE_FORMAT) {
+        return Ok(until);
+    }
+
+    let (hour, minute) = parse_time(until)
+        .ok_or_else(|| format!("❌ Could not read [{}] as YYYY-MM-DD HH:MM or HH:MM", until))?;
+
+    let time = NaiveTime::from_hms_opt(hour as u32, minute as u32, 0).unwrap_or(NaiveTime::MIN);
+    let today = now.date().and_time(time);
+
+    Ok(if today > *now {
+        today
+    } else {
+        today + Duration::days(1)
+    })
+}
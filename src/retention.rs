@@ -0,0 +1,138 @@
+// Standards ─────────────────────────────────────────────────────
+use std::collections::{HashMap, HashSet};
+
+// Crates ────────────────────────────────────────────────────────
+use chrono::{Duration, Local, NaiveDateTime};
+use rusqlite::Connection;
+
+// mods ──────────────────────────────────────────────────────────
+use crate::{
+    consts::{
+        DUE_FORMAT, LOG_STAMP_FORMAT, PRUNE_INTERVAL_HOURS, RETENTION_DAYS,
+        RETENTION_FAILURE_DAYS, RETENTION_RUNS_PER_JOB, SUCCESS,
+    },
+    db::db::{
+        delete_logs, get_last_prune, get_log_entries, init_db, set_last_prune, vacuum_if_needed,
+    },
+};
+
+// Retention ─────────────────────────────────────────────────────
+
+// The logs the retention policy lets go, and how many results they hold
+struct Pruning {
+    log_ids: Vec<u16>,
+    results: usize,
+}
+
+// A log is kept while it is younger than RETENTION_DAYS, a failure younger than
+// RETENTION_FAILURE_DAYS, or among the RETENTION_RUNS_PER_JOB latest runs of one of its jobs
+fn plan(conn: &Connection, now: &NaiveDateTime) -> Pruning {
+    let (logs, log_jobs) = get_log_entries(conn);
+
+    let mut results: HashMap<u16, usize> = HashMap::new();
+    let mut runs_by_job: HashMap<u16, Vec<u16>> = HashMap::new();
+    for (log_id, job_id) in log_jobs {
+        *results.entry(log_id).or_default() += 1;
+        if let Some(job_id) = job_id {
+            runs_by_job.entry(job_id).or_default().push(log_id);
+        }
+    }
+
+    let latest_runs: HashSet<u16> = runs_by_job
+        .into_values()
+        .flat_map(|mut log_ids| {
+            log_ids.sort_unstable_by(|a, b| b.cmp(a));
+            log_ids.dedup();
+            log_ids.truncate(RETENTION_RUNS_PER_JOB);
+            log_ids
+        })
+        .collect();
+
+    let log_ids: Vec<u16> = logs
+        .iter()
+        .filter(|log| {
+            let log_id = log.id.unwrap();
+            // A stamp it cannot read is kept rather than guessed
+            let Ok(started) = NaiveDateTime::parse_from_str(&log.startstamp, LOG_STAMP_FORMAT)
+            else {
+                return false;
+            };
+            let age = *now - started;
+
+            !latest_runs.contains(&log_id)
+                && age > Duration::days(RETENTION_DAYS)
+                && (log.status == SUCCESS || age > Duration::days(RETENTION_FAILURE_DAYS))
+        })
+        .map(|log| log.id.unwrap())
+        .collect();
+
+    Pruning {
+        results: log_ids.iter().filter_map(|id| results.get(id)).sum(),
+        log_ids,
+    }
+}
+
+// Deletes what `pruning` holds, then vacuums if that freed enough. Returns whether it did
+fn apply(conn: &mut Connection, pruning: &Pruning) -> Result<bool, String> {
+    if pruning.log_ids.is_empty() {
+        return Ok(false);
+    }
+
+    delete_logs(conn, &pruning.log_ids)?;
+    vacuum_if_needed(conn)
+}
+
+fn report(pruning: &Pruning, vacuumed: bool) {
+    println!(
+        "🧹 Pruned {} logs ({} results) from the journal",
+        pruning.log_ids.len(),
+        pruning.results
+    );
+    if vacuumed {
+        println!("🗜️ Vacuumed the database");
+    }
+}
+
+// `syncrab prune [--dry-run]`
+pub fn prune(dry_run: bool) -> Result<(), String> {
+    let mut conn = init_db();
+    let now = Local::now().naive_local();
+    let pruning = plan(&conn, &now);
+
+    if dry_run {
+        println!(
+            "📝 Would prune {} logs ({} results) from the journal",
+            pruning.log_ids.len(),
+            pruning.results
+        );
+        return Ok(());
+    }
+
+    let vacuumed = apply(&mut conn, &pruning)?;
+    set_last_prune(&mut conn, &now.format(DUE_FORMAT).to_string())?;
+    report(&pruning, vacuumed);
+    Ok(())
+}
+
+// What the runners call: prunes once PRUNE_INTERVAL_HOURS went by since the last time, whoever did it
+pub fn prune_if_due(conn: &mut Connection, now: &NaiveDateTime) {
+    let is_due = get_last_prune(conn)
+        .and_then(|pruned_at| NaiveDateTime::parse_from_str(&pruned_at, DUE_FORMAT).ok())
+        .is_none_or(|pruned_at| *now - pruned_at >= Duration::hours(PRUNE_INTERVAL_HOURS));
+    if !is_due {
+        return;
+    }
+
+    // Marked first: a failing prune is not retried on every run
+    if let Err(error) = set_last_prune(conn, &now.format(DUE_FORMAT).to_string()) {
+        eprintln!("{}", error);
+        return;
+    }
+
+    let pruning = plan(conn, now);
+    match apply(conn, &pruning) {
+        Ok(vacuumed) if !pruning.log_ids.is_empty() => report(&pruning, vacuumed),
+        Ok(_) => {}
+        Err(error) => eprintln!("{}", error),
+    }
+}
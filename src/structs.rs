@@ -1,33 +1,61 @@
 // Standards ─────────────────────────────────────────────────────
 use std::{
-    sync::mpsc::Sender,
     borrow::Cow,
+    path::PathBuf,
+    time::Duration,
 };
 
 // Crates ───────────────────────────────────────────────────────
-use chrono::Local;
-use notify::{Event, RecommendedWatcher};
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use notify::PollWatcher;
 use serde::{Deserialize, Serialize};
 
 // mods ─────────────────────────────────────────────────────────
 use crate::{
-    consts::{DAILY, REAL_TIME, WEEKLY},
-    utils::status_emoji,
+    consts::{
+        BLACKOUT, DAILY, DAYS_SEPARATOR, DUE_FORMAT, EMOJI_GUARD_MARKER, EMOJI_GUARD_MOUNT,
+        GUARD_MARKER, GUARD_MOUNT, GUARD_NONE, GUARDS, LOG_STAMP_FORMAT, MOUNT, PAUSE, PER_MOUNT, REAL_TIME,
+        MAX_RETRY_WAIT_MINUTES, RETRY_BACKOFF, RETRY_DELAY, WEEK_DAYS, WEEKLY, EMOJI_WATCHING, EMOJI_PAUSED, EMOJI_QUEUED,
+        EMOJI_ERRORS, EMOJI_WATCH_FAILED, WATCH_FAILED, WATCH_NATIVE, WATCH_POLLING, EMOJI_POLLING,
+        POLL_INTERVAL_SECS,
+    },
+    utils::{capitalise, log_status_emoji, parse_time, status_emoji},
 };
 
 // Structs & Enums ──────────────────────────────────────────────
 
 // Job
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub struct Job {
     pub id: Option<u16>,
     pub source: String,
     pub target: String,
     pub frequency: String,
     pub hour: u8,
-    pub day: Option<String>,
+    pub minute: u8,
+    pub days: Vec<String>,
     pub mirror: u8,
     pub active: u8,
+    pub retry_max: u8,
+    pub retry_delay: u16,
+    pub retry_backoff: f64,
+    pub timeout: u16,
+    pub target_guard: String,
+    pub mount_trigger: String,
+    pub once_per: String,
+    pub watch_backend: String,
+    pub poll_interval: u16,
+    // From the journal, not stored with the job
+    #[serde(skip)]
+    pub last_run: Option<NaiveDateTime>,
+    #[serde(skip)]
+    pub last_status: Option<String>,
+    // From syncrab_w's control socket, real-time jobs only
+    #[serde(skip)]
+    pub watch_status: Option<WatchStatus>,
+    // From syncrab_w's watch states (watching or failed), while it is not down
+    #[serde(skip)]
+    pub watch_state: Option<String>,
 }
 
 impl Job {
@@ -38,54 +66,183 @@ impl Job {
             target: String::new(),
             frequency: frequency.to_string(),
             hour: 0,
-            day: None,
+            minute: 0,
+            days: Vec::new(),
             mirror: 0,
             active: 0,
+            retry_max: 0,
+            retry_delay: RETRY_DELAY,
+            retry_backoff: RETRY_BACKOFF,
+            timeout: 0,
+            target_guard: GUARD_NONE.to_string(),
+            mount_trigger: String::new(),
+            once_per: PER_MOUNT.to_string(),
+            watch_backend: WATCH_NATIVE.to_string(),
+            poll_interval: POLL_INTERVAL_SECS,
+            last_run: None,
+            last_status: None,
+            watch_status: None,
+            watch_state: None,
         }
     }
 
-    pub fn get_fields_data(&self) -> Vec<Cow<'_, str>> {
-        let hour = self.hour.to_string();
-        let formatted_hour = if hour.len() == 1 {
-            Cow::Owned(format!("0{}", hour))
+    // Parse the comma separated `day` column into an ordered set of week days
+    pub fn parse_days(days: Option<String>) -> Vec<String> {
+        let days: Vec<String> = days
+            .unwrap_or_default()
+            .split(DAYS_SEPARATOR)
+            .map(str::trim)
+            .filter(|day| !day.is_empty())
+            .map(capitalise)
+            .collect();
+
+        WEEK_DAYS
+            .iter()
+            .filter(|week_day| days.iter().any(|day| day == *week_day))
+            .map(|week_day| week_day.to_string())
+            .collect()
+    }
+
+    // Serialise the week days back into the `day` column
+    pub fn days_to_string(&self) -> Option<String> {
+        if self.days.is_empty() {
+            None
         } else {
-            Cow::Owned(hour)
-        };
+            Some(self.days.join(&DAYS_SEPARATOR.to_string()))
+        }
+    }
+
+    pub fn formatted_time(&self) -> String {
+        format!("{:02}:{:02}", self.hour, self.minute)
+    }
+
+    // None when the job may run for as long as it needs
+    pub fn timeout_duration(&self) -> Option<Duration> {
+        (self.timeout > 0).then(|| Duration::from_secs(self.timeout as u64 * 60))
+    }
+
+    // Minutes to wait before the given retry attempt (1-based), at most MAX_RETRY_WAIT_MINUTES
+    // however the backoff grows
+    pub fn retry_wait(&self, attempt: u8) -> i64 {
+        let factor = self.retry_backoff.powi(attempt.saturating_sub(1) as i32);
+        (self.retry_delay as f64 * factor)
+            .round()
+            .clamp(0.0, MAX_RETRY_WAIT_MINUTES as f64) as i64
+    }
+
+    // When the scheduler runs the job next, None for inactive, real-time & on-mount jobs
+    pub fn next_run(&self, now: &NaiveDateTime) -> Option<NaiveDateTime> {
+        if self.active == 0 {
+            return None;
+        }
+
+        let time = NaiveTime::from_hms_opt(self.hour as u32, self.minute as u32, 0)?;
+
+        (0..=7)
+            .map(|offset| (now.date() + chrono::Duration::days(offset)).and_time(time))
+            .filter(|at| at > now)
+            .find(|at| match self.frequency.as_str() {
+                DAILY => true,
+                WEEKLY => self.days.contains(&at.weekday().to_string()),
+                _ => false,
+            })
+    }
+
+    pub fn formatted_last_run(&self) -> String {
+        self.last_run
+            .map(|last_run| last_run.format(LOG_STAMP_FORMAT).to_string())
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    pub fn formatted_next_run(&self) -> String {
+        self.next_run(&Local::now().naive_local())
+            .map(|next_run| next_run.format(LOG_STAMP_FORMAT).to_string())
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    pub fn formatted_last_status(&self) -> String {
+        match &self.last_status {
+            Some(status) => log_status_emoji(status).to_string(),
+            None => "-".to_string(),
+        }
+    }
+
+    pub fn formatted_watch_status(&self) -> String {
+        match (&self.watch_status, self.watch_state.as_deref()) {
+            (Some(status), _) => status.formatted(),
+            (None, Some(WATCH_FAILED)) => format!("{} {}", EMOJI_WATCH_FAILED, WATCH_FAILED),
+            (None, Some(_)) => EMOJI_WATCHING.to_string(),
+            (None, None) => "-".to_string(),
+        }
+    }
 
+    // The guard that follows the current one: none → mount → marker → none
+    pub fn next_guard(&self) -> String {
+        let idx = GUARDS
+            .iter()
+            .position(|guard| *guard == self.target_guard)
+            .unwrap_or_default();
+        GUARDS[(idx + 1) % GUARDS.len()].to_string()
+    }
+
+    pub fn formatted_guard(&self) -> String {
+        match self.target_guard.as_str() {
+            GUARD_MOUNT => format!("{} {}", EMOJI_GUARD_MOUNT, GUARD_MOUNT),
+            GUARD_MARKER => format!("{} {}", EMOJI_GUARD_MARKER, GUARD_MARKER),
+            _ => "-".to_string(),
+        }
+    }
+
+    pub fn get_fields_data(&self) -> Vec<Cow<'_, str>> {
         match self.frequency.as_str() {
             REAL_TIME => vec![
                 Cow::Owned(self.id.unwrap().to_string()),
                 Cow::Borrowed(&self.source),
                 Cow::Borrowed(&self.target),
                 Cow::Owned(status_emoji(self.mirror)),
+                Cow::Owned(self.formatted_guard()),
+                Cow::Owned(self.formatted_last_run()),
+                Cow::Owned(self.formatted_last_status()),
+                Cow::Owned(self.formatted_watch_status()),
                 Cow::Owned(status_emoji(self.active)),
             ],
             DAILY => vec![
                 Cow::Owned(self.id.unwrap().to_string()),
                 Cow::Borrowed(&self.source),
                 Cow::Borrowed(&self.target),
-                formatted_hour,
+                Cow::Owned(self.formatted_time()),
                 Cow::Owned(status_emoji(self.mirror)),
+                Cow::Owned(self.formatted_guard()),
+                Cow::Owned(self.formatted_last_run()),
+                Cow::Owned(self.formatted_last_status()),
+                Cow::Owned(self.formatted_next_run()),
+                Cow::Owned(status_emoji(self.active)),
+            ],
+            WEEKLY => vec![
+                Cow::Owned(self.id.unwrap().to_string()),
+                Cow::Borrowed(&self.source),
+                Cow::Borrowed(&self.target),
+                Cow::Owned(self.formatted_time()),
+                Cow::Owned(self.days.join(&DAYS_SEPARATOR.to_string())),
+                Cow::Owned(status_emoji(self.mirror)),
+                Cow::Owned(self.formatted_guard()),
+                Cow::Owned(self.formatted_last_run()),
+                Cow::Owned(self.formatted_last_status()),
+                Cow::Owned(self.formatted_next_run()),
+                Cow::Owned(status_emoji(self.active)),
+            ],
+            MOUNT => vec![
+                Cow::Owned(self.id.unwrap().to_string()),
+                Cow::Borrowed(&self.source),
+                Cow::Borrowed(&self.target),
+                Cow::Borrowed(&self.mount_trigger),
+                Cow::Borrowed(&self.once_per),
+                Cow::Owned(status_emoji(self.mirror)),
+                Cow::Owned(self.formatted_guard()),
+                Cow::Owned(self.formatted_last_run()),
+                Cow::Owned(self.formatted_last_status()),
                 Cow::Owned(status_emoji(self.active)),
             ],
-            WEEKLY => {
-                let day = self.day.as_deref().unwrap_or_default();
-                let formatted_day = if day.len() == 1 {
-                    Cow::Owned(format!("0{}", day))
-                } else {
-                    Cow::Borrowed(day)
-                };
-
-                vec![
-                    Cow::Owned(self.id.unwrap().to_string()),
-                    Cow::Borrowed(&self.source),
-                    Cow::Borrowed(&self.target),
-                    formatted_hour,
-                    formatted_day,
-                    Cow::Owned(status_emoji(self.mirror)),
-                    Cow::Owned(status_emoji(self.active)),
-                ]
-            }
             _ => panic!(
                 "❌ Failed to get fields data from job record because [{}] is not a valid frequency",
                 self.frequency
@@ -123,6 +280,8 @@ pub struct Log {
     pub status: String,
     pub success_count: u16,
     pub failed_count: u16,
+    pub retry_of: Option<u16>,
+    pub attempt: u8,
     pub log_results: Option<Vec<LogResult>>,
 }
 
@@ -135,10 +294,28 @@ impl Log {
             status: String::new(),
             success_count: 0,
             failed_count: 0,
+            retry_of: None,
+            attempt: 0,
             log_results: None,
         }
     }
 
+    pub fn new_retry(pending_run: &PendingRun) -> Self {
+        Self {
+            retry_of: pending_run.origin_log_id,
+            attempt: pending_run.attempt,
+            ..Self::new()
+        }
+    }
+
+    pub fn formatted_attempt(&self) -> String {
+        match (self.attempt, self.retry_of) {
+            (0, _) => "-".to_string(),
+            (attempt, Some(origin)) => format!("{} of #{}", attempt, origin),
+            (attempt, None) => attempt.to_string(),
+        }
+    }
+
     pub fn get_fields_data(&self) -> Vec<String> {
         vec![
             self.id.unwrap().to_string(),
@@ -147,6 +324,7 @@ impl Log {
             self.status.to_string(),
             self.success_count.to_string(),
             self.failed_count.to_string(),
+            self.formatted_attempt(),
         ]
     }
 }
@@ -155,6 +333,7 @@ impl Log {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LogResult {
     pub log_id: Option<u16>,
+    pub job_id: Option<u16>,
     pub frequency: String,
     pub message: String,
     pub source: String,
@@ -162,13 +341,14 @@ pub struct LogResult {
 }
 
 impl LogResult {
-    pub fn new(frequency: &str, message: &str, source: &str, target: &str) -> Self {
+    pub fn new(frequency: &str, message: &str, job: &Job) -> Self {
         Self {
             log_id: None,
+            job_id: job.id,
             frequency: frequency.to_string(),
             message: message.to_string(),
-            source: source.to_string(),
-            target: target.to_string(),
+            source: job.source.to_string(),
+            target: job.target.to_string(),
         }
     }
 
@@ -182,9 +362,192 @@ impl LogResult {
     }
 }
 
+// PendingRun
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PendingRun {
+    pub id: Option<u16>,
+    pub job_id: u16,
+    pub due_at: String,
+    pub attempt: u8,
+    pub origin_log_id: Option<u16>,
+    pub reason: String,
+}
+
+impl PendingRun {
+    pub fn new(job_id: u16, due_at: String, attempt: u8, origin_log_id: Option<u16>, reason: &str) -> Self {
+        Self {
+            id: None,
+            job_id,
+            due_at,
+            attempt,
+            origin_log_id,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+// SchedulePolicy
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SchedulePolicy {
+    pub id: Option<u16>,
+    pub kind: String,
+    // Blackout window: the days it applies to and its "HH:MM" bounds
+    pub days: Vec<String>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    // Pause: when it lifts
+    pub until: Option<String>,
+}
+
+impl SchedulePolicy {
+    pub fn new_pause(until: &NaiveDateTime) -> Self {
+        Self {
+            id: None,
+            kind: PAUSE.to_string(),
+            days: Vec::new(),
+            start: None,
+            end: None,
+            until: Some(until.format(DUE_FORMAT).to_string()),
+        }
+    }
+
+    pub fn new_blackout(days: Vec<String>, start: (u8, u8), end: (u8, u8)) -> Self {
+        Self {
+            id: None,
+            kind: BLACKOUT.to_string(),
+            days,
+            start: Some(format!("{:02}:{:02}", start.0, start.1)),
+            end: Some(format!("{:02}:{:02}", end.0, end.1)),
+            until: None,
+        }
+    }
+
+    // When the block this policy puts on `now` lifts, None when it doesn't block it
+    pub fn blocks_until(&self, now: &NaiveDateTime) -> Option<NaiveDateTime> {
+        match self.kind.as_str() {
+            PAUSE => {
+                let until =
+                    NaiveDateTime::parse_from_str(self.until.as_deref()?, DUE_FORMAT).ok()?;
+                (*now < until).then_some(until)
+            }
+            BLACKOUT => {
+                let start = to_naive_time(self.start.as_deref()?)?;
+                let end = to_naive_time(self.end.as_deref()?)?;
+                let applies_on =
+                    |date: NaiveDate| self.days.iter().any(|day| *day == date.weekday().to_string());
+
+                let (today, time) = (now.date(), now.time());
+                if start < end {
+                    (applies_on(today) && start <= time && time < end).then(|| today.and_time(end))
+                } else if applies_on(today) && time >= start {
+                    // The window runs past midnight
+                    Some(today.succ_opt()?.and_time(end))
+                } else {
+                    (applies_on(today.pred_opt()?) && time < end).then(|| today.and_time(end))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self.kind.as_str() {
+            PAUSE => format!("paused until {}", self.until.as_deref().unwrap_or("-")),
+            _ => format!(
+                "no backups on {} from {} to {}",
+                self.days.join(&DAYS_SEPARATOR.to_string()),
+                self.start.as_deref().unwrap_or("-"),
+                self.end.as_deref().unwrap_or("-")
+            ),
+        }
+    }
+}
+
+fn to_naive_time(time: &str) -> Option<NaiveTime> {
+    let (hour, minute) = parse_time(time)?;
+    NaiveTime::from_hms_opt(hour as u32, minute as u32, 0)
+}
+
+// Mount, one line of the mountinfo file
+#[derive(Debug, Clone)]
+pub struct Mount {
+    pub id: u32,
+    pub mount_point: PathBuf,
+    pub device: String,
+}
+
+// WatchStatus
+// What syncrab_w reports about a job it watches
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct WatchStatus {
+    pub job_id: u16,
+    pub source: String,
+    pub target: String,
+    pub paused: bool,
+    // native or polling, the latter also when the native watch failed
+    pub backend: String,
+    // Paths waiting for their events to settle
+    pub queued: usize,
+    pub last_event: Option<String>,
+    pub last_sync: Option<String>,
+    pub syncs: u32,
+    pub errors: u32,
+}
+
+impl WatchStatus {
+    pub fn new(job: &Job) -> Self {
+        Self {
+            job_id: job.id.unwrap(),
+            source: job.source.to_string(),
+            target: job.target.to_string(),
+            ..Self::default()
+        }
+    }
+
+    // 👀 (🔁 when polled) or ⏸️, then the queue & the errors when there are some: "👀 ⏳3 ❗1"
+    pub fn formatted(&self) -> String {
+        let mut formatted = if self.paused {
+            EMOJI_PAUSED.to_string()
+        } else if self.backend == WATCH_POLLING {
+            EMOJI_POLLING.to_string()
+        } else {
+            EMOJI_WATCHING.to_string()
+        };
+        if self.queued > 0 {
+            formatted.push_str(&format!(" {}{}", EMOJI_QUEUED, self.queued));
+        }
+        if self.errors > 0 {
+            formatted.push_str(&format!(" {}{}", EMOJI_ERRORS, self.errors));
+        }
+        formatted
+    }
+}
+
+// Heartbeat
+// Written by syncrab_w every HEARTBEAT_SECS, times in HEARTBEAT_FORMAT
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Heartbeat {
+    pub pid: u32,
+    pub started_at: String,
+    pub beat_at: String,
+    pub watched_count: u16,
+    pub last_event_at: Option<String>,
+}
+
+// WatchState
+// Whether syncrab_w managed to watch a real-time job, and why not
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WatchState {
+    pub job_id: u16,
+    pub state: String,
+    pub error: Option<String>,
+}
+
 // WatchedJob
 pub struct WatchedJob {
     pub job: Job,
-    pub job_watcher: RecommendedWatcher,
-    pub job_tx: Sender<Result<Event, notify::Error>>,
+    // Normalised: the shared native watch's events reach the job by this prefix
+    pub source: PathBuf,
+    // Polled jobs only, native ones share syncrab_w's watch
+    pub poll_watcher: Option<PollWatcher>,
 }
@@ -4,15 +4,19 @@ use std::{
     env,
     ffi::OsString,
     fs::metadata,
-    fs::{OpenOptions, copy, create_dir_all, read_dir, remove_dir_all, remove_file},
+    fs::{
+        File, OpenOptions, copy, create_dir_all, read_dir, read_to_string, remove_dir_all,
+        remove_file, rename, set_permissions,
+    },
     io::Write,
+    os::unix::fs::MetadataExt,
     path::{Path, PathBuf},
     process::Command,
 };
 
 // Crates ────────────────────────────────────────────────────────
 use rusqlite::Connection;
-use chrono::Local;
+use chrono::{Duration, Local, NaiveDateTime};
 use ratatui::{
     layout::{Alignment, Constraint},
     style::{Color, Modifier, Stylize},
@@ -25,17 +29,27 @@ use crate::{
     consts::{
         DAILY, DAILY_BACKUPS, DAILY_COLS, FAILED, JOURNAL, JOURNAL_COLS, LOG, LOG_COLS, LOG_PATH,
         PARTIAL, REAL_TIME, REAL_TIME_BACKUPS, REAL_TIME_COLS, SUCCESS, WEEKLY, WEEKLY_BACKUPS,
-        WEEKLY_COLS, EMOJI_ACTIVE, EMOJI_INACTIVE
+        WEEKLY_COLS, EMOJI_ACTIVE, EMOJI_INACTIVE, DEFERRED, DUE_FORMAT, RETRY, GUARD_MARKER,
+        GUARD_MOUNT, MOUNTINFO, TARGET_MARKER, DISK_BY_UUID, UUID_PREFIX, MOUNT, MOUNT_BACKUPS,
+        MOUNT_COLS, CANCELLED, TIMED_OUT, EMOJI_STATUS_CANCELLED, EMOJI_STATUS_FAILED,
+        EMOJI_STATUS_OTHER, EMOJI_STATUS_PARTIAL, EMOJI_STATUS_SUCCESS, EMOJI_STATUS_TIMED_OUT,
+        TRASH_DIR, TRASH_FORMAT, USE_TRASH, ALIVE, DOWN, HEARTBEAT_FORMAT, HEARTBEAT_STALE_SECS,
+        PROC, STALE,
+    },
+    db::{
+        db::{get_job, has_pending_run, insert_log, insert_log_resuts, insert_pending_run},
+        query::JobQuery,
     },
-    db::db::{insert_log, insert_log_resuts},
-    structs::{Job, Log, LogResult, Stat},
+    lock::CancelToken,
+    structs::{Heartbeat, Job, Log, LogResult, Mount, PendingRun, SchedulePolicy, Stat},
 };
 
 pub fn get_stats(jobs_by_freq: &HashMap<&'static str, Vec<Job>>) -> HashMap<&'static str, Stat> {
-    let mut stats_by_freq: HashMap<&'static str, Stat> = HashMap::with_capacity(3);
+    let mut stats_by_freq: HashMap<&'static str, Stat> = HashMap::with_capacity(4);
     stats_by_freq.insert(DAILY, Stat::new(DAILY_BACKUPS));
     stats_by_freq.insert(WEEKLY, Stat::new(WEEKLY_BACKUPS));
     stats_by_freq.insert(REAL_TIME, Stat::new(REAL_TIME_BACKUPS));
+    stats_by_freq.insert(MOUNT, Stat::new(MOUNT_BACKUPS));
 
     for (freq, jobs) in jobs_by_freq {
         let stat = stats_by_freq.get_mut(freq).unwrap();
@@ -67,13 +81,23 @@ pub fn get_columns_info_by_key(
                 Constraint::Length(3),
                 Constraint::Ratio(1, 2),
                 Constraint::Ratio(1, 2),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(16),
+                Constraint::Length(6),
                 Constraint::Length(12),
+                Constraint::Length(8),
             ],
             &[
                 Alignment::Center,
                 Alignment::Left,
                 Alignment::Left,
                 Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
             ],
         ),
         DAILY => (
@@ -84,6 +108,10 @@ pub fn get_columns_info_by_key(
                 Constraint::Ratio(1, 2),
                 Constraint::Length(8),
                 Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(16),
+                Constraint::Length(6),
+                Constraint::Length(16),
                 Constraint::Length(8),
             ],
             &[
@@ -93,6 +121,10 @@ pub fn get_columns_info_by_key(
                 Alignment::Center,
                 Alignment::Center,
                 Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
             ],
         ),
         WEEKLY => (
@@ -102,14 +134,49 @@ pub fn get_columns_info_by_key(
                 Constraint::Ratio(1, 2),
                 Constraint::Ratio(1, 2),
                 Constraint::Length(8),
+                Constraint::Length(27),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(16),
+                Constraint::Length(6),
+                Constraint::Length(16),
+                Constraint::Length(8),
+            ],
+            &[
+                Alignment::Center,
+                Alignment::Left,
+                Alignment::Left,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+                Alignment::Center,
+            ],
+        ),
+        MOUNT => (
+            MOUNT_COLS,
+            &[
+                Constraint::Length(3),
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
                 Constraint::Length(8),
                 Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(16),
+                Constraint::Length(6),
                 Constraint::Length(8),
             ],
             &[
                 Alignment::Center,
                 Alignment::Left,
                 Alignment::Left,
+                Alignment::Left,
+                Alignment::Center,
+                Alignment::Center,
                 Alignment::Center,
                 Alignment::Center,
                 Alignment::Center,
@@ -125,6 +192,7 @@ pub fn get_columns_info_by_key(
                 Constraint::Fill(1),
                 Constraint::Fill(1),
                 Constraint::Fill(1),
+                Constraint::Length(12),
             ],
             &[
                 Alignment::Center,
@@ -133,6 +201,7 @@ pub fn get_columns_info_by_key(
                 Alignment::Left,
                 Alignment::Center,
                 Alignment::Center,
+                Alignment::Center,
             ],
         ),
         LOG => (
@@ -168,6 +237,53 @@ pub fn capitalise(s: &str) -> String {
     s.get(0..1).unwrap().to_uppercase() + &s[1..].to_lowercase()
 }
 
+// When the policies stop blocking scheduled backups, None when they don't block `now`.
+// Follows overlapping policies, e.g. a pause that ends in the middle of a blackout
+pub fn blocked_until(policies: &[SchedulePolicy], now: &NaiveDateTime) -> Option<NaiveDateTime> {
+    let mut until: Option<NaiveDateTime> = None;
+
+    // Bounded, in case the policies chain into each other for good
+    for _ in 0..=policies.len() {
+        let at = until.unwrap_or(*now);
+        match policies.iter().find_map(|policy| policy.blocks_until(&at)) {
+            Some(next) => until = Some(next),
+            None => break,
+        }
+    }
+
+    until
+}
+
+// Down when syncrab_w never beat or its process is gone, stale when it still runs
+// but has not beaten for HEARTBEAT_STALE_SECS (e.g. stuck), alive otherwise
+pub fn watcher_health(heartbeat: Option<&Heartbeat>, now: &NaiveDateTime) -> &'static str {
+    let Some(heartbeat) = heartbeat else {
+        return DOWN;
+    };
+
+    if !Path::new(PROC).join(heartbeat.pid.to_string()).exists() {
+        return DOWN;
+    }
+
+    match NaiveDateTime::parse_from_str(&heartbeat.beat_at, HEARTBEAT_FORMAT) {
+        Ok(beat_at) if (*now - beat_at).num_seconds() <= HEARTBEAT_STALE_SECS => ALIVE,
+        _ => STALE,
+    }
+}
+
+// Parse a "HH:MM" (or a bare "HH") time into its hour and minute
+pub fn parse_time(time: &str) -> Option<(u8, u8)> {
+    let (hour, minute) = match time.trim().split_once(':') {
+        Some((hour, minute)) => (hour, minute),
+        None => (time.trim(), "0"),
+    };
+
+    match (hour.parse::<u8>(), minute.parse::<u8>()) {
+        (Ok(h), Ok(m)) if h <= 23 && m <= 59 => Some((h, m)),
+        _ => None,
+    }
+}
+
 pub fn normalise_path(path: &str) -> PathBuf {
     if path.starts_with("~/") {
         if let Some(home) = env::var_os("HOME") {
@@ -177,13 +293,29 @@ pub fn normalise_path(path: &str) -> PathBuf {
     PathBuf::from(path)
 }
 
+// Returns how many files and folders it had to copy or delete to match the source.
+// `trash_root`: the target root whose trash takes the orphans (see remove_from_target),
+// None deletes them outright
 pub fn copy_dir(
     source: &PathBuf,
     target: &PathBuf,
     mirror: u8,
     total: usize,
     count: &mut usize,
-) -> Result<(), String> {
+    cancel: &CancelToken,
+    trash_root: Option<&Path>,
+) -> Result<usize, String> {
+    let mut changed = 0;
+
+    if let Some(interrupt) = cancel.check() {
+        return Err(format!(
+            "Run {} after {}/{} files",
+            interrupt.to_str(),
+            count,
+            total
+        ));
+    }
+
     if source.is_file() {
         // Create parent dir if it doesn't exist
         if let Some(parent) = target.parent() {
@@ -205,6 +337,7 @@ pub fn copy_dir(
                     e
                 )
             })?;
+            changed += 1;
         }
 
         *count += 1;
@@ -236,10 +369,12 @@ pub fn copy_dir(
             })?;
 
         if mirror == 1 {
-            let source_filenames: HashSet<OsString> = source_entries
+            let mut source_filenames: HashSet<OsString> = source_entries
                 .iter()
                 .map(|entry| entry.file_name())
                 .collect();
+            // Syncrab's own files are never orphans
+            source_filenames.extend([TARGET_MARKER, TRASH_DIR].map(OsString::from));
 
             if let Ok(target_itr) = read_dir(target) {
                 for entry in target_itr {
@@ -247,6 +382,12 @@ pub fn copy_dir(
                         // If the target file/dir does NOT exist in source, delete it
                         if !source_filenames.contains(&entry.file_name()) {
                             let path = entry.path();
+                            if let Some(trash_root) = trash_root {
+                                remove_from_target(trash_root, &path)?;
+                                changed += 1;
+                                continue;
+                            }
+
                             let result = if path.is_dir() {
                                 remove_dir_all(&path)
                             } else {
@@ -260,6 +401,7 @@ pub fn copy_dir(
                                     e
                                 ));
                             }
+                            changed += 1;
                         }
                     }
                 }
@@ -269,7 +411,7 @@ pub fn copy_dir(
         for entry in source_entries {
             let path = entry.path();
             let new_target = target.join(entry.file_name());
-            copy_dir(&path, &new_target, mirror, total, count)?;
+            changed += copy_dir(&path, &new_target, mirror, total, count, cancel, trash_root)?;
         }
     } else {
         return Err(format!(
@@ -278,7 +420,97 @@ pub fn copy_dir(
         ));
     }
 
-    Ok(())
+    Ok(changed)
+}
+
+// Deletes a path of the target, or moves it into the target's trash when USE_TRASH is on
+pub fn remove_from_target(target_root: &Path, path: &Path) -> Result<(), String> {
+    if USE_TRASH {
+        let relative = path.strip_prefix(target_root).unwrap_or(path);
+        let trashed = target_root
+            .join(TRASH_DIR)
+            .join(Local::now().format(TRASH_FORMAT).to_string())
+            .join(relative);
+
+        if let Some(parent) = trashed.parent() {
+            create_dir_all(parent).map_err(|e| {
+                format!(
+                    "Could not create trash directory [{}] because {}",
+                    parent.display(),
+                    e
+                )
+            })?;
+        }
+
+        return rename(path, &trashed).map_err(|e| {
+            format!(
+                "Failed to move [{}] to the trash because [{}]",
+                path.display(),
+                e
+            )
+        });
+    }
+
+    let result = if path.is_dir() {
+        remove_dir_all(path)
+    } else {
+        remove_file(path)
+    };
+
+    result.map_err(|e| format!("Failed to delete [{}] because [{}]", path.display(), e))
+}
+
+// Gives the target's copy the source's permissions & modification time, without copying it again
+pub fn sync_metadata(source: &Path, target: &Path) -> Result<(), String> {
+    let source_metadata = metadata(source).map_err(|e| {
+        format!(
+            "Could not get metadata of the source [{}] because [{}]",
+            source.display(),
+            e
+        )
+    })?;
+
+    // Before the permissions, they may forbid opening it
+    source_metadata
+        .modified()
+        .and_then(|modified| File::open(target)?.set_modified(modified))
+        .map_err(|e| {
+            format!(
+                "Failed to set the modified time of [{}] because [{}]",
+                target.display(),
+                e
+            )
+        })?;
+
+    set_permissions(target, source_metadata.permissions()).map_err(|e| {
+        format!(
+            "Failed to set the permissions of [{}] because [{}]",
+            target.display(),
+            e
+        )
+    })
+}
+
+// Moves what the source renamed under its new name in the target, rather than copying it again
+pub fn rename_in_target(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        create_dir_all(parent).map_err(|e| {
+            format!(
+                "Could not create parent directory [{}] because {}",
+                parent.display(),
+                e
+            )
+        })?;
+    }
+
+    rename(from, to).map_err(|e| {
+        format!(
+            "Failed to rename [{}] to [{}] because [{}]",
+            from.display(),
+            to.display(),
+            e
+        )
+    })
 }
 
 fn should_copy(source: &PathBuf, target: &PathBuf) -> Result<bool, String> {
@@ -388,16 +620,14 @@ pub fn are_paths_valid(
         failed_directories.push(LogResult::new(
             freq_str,
             "Source path must be absolute",
-            &job.source,
-            &job.target,
+            job,
         ));
         return false;
     } else if !source.exists() {
         failed_directories.push(LogResult::new(
             freq_str,
             "Source path does not exist",
-            &job.source,
-            &job.target,
+            job,
         ));
         return false;
     }
@@ -407,45 +637,170 @@ pub fn are_paths_valid(
             failed_directories.push(LogResult::new(
                 freq_str,
                 "Target path must be absolute",
-                &job.source,
-                &job.target,
+                job,
             ));
             return false;
         } else if target.is_file() {
             failed_directories.push(LogResult::new(
                 freq_str,
                 "Target path must be a directory",
-                &job.source,
-                &job.target,
+                job,
             ));
             return false;
         }
     }
 
+    if let Err(message) = check_target_guard(job, target) {
+        failed_directories.push(LogResult::new(freq_str, &message, job));
+        return false;
+    }
+
     true
 }
 
+// Refuse targets whose drive is not there, so an unmounted USB or network share
+// does not silently fill up the root filesystem.
+// The drive root is the mount point of an on-mount job's trigger, else the target itself
+pub fn check_target_guard(job: &Job, target: &Path) -> Result<(), String> {
+    if job.target_guard != GUARD_MOUNT && job.target_guard != GUARD_MARKER {
+        return Ok(());
+    }
+
+    let mounts = get_mounts()?;
+    let drive_root = match job.frequency.as_str() {
+        MOUNT if !job.mount_trigger.trim().is_empty() => {
+            let mount = find_trigger_mount(&job.mount_trigger, &mounts).ok_or_else(|| {
+                format!("Target drive [{}] is not mounted", job.mount_trigger.trim())
+            })?;
+            if !target.starts_with(&mount.mount_point) {
+                return Err(format!(
+                    "Target [{}] is not on the drive mounted at [{}]",
+                    target.display(),
+                    mount.mount_point.display()
+                ));
+            }
+            mount.mount_point.clone()
+        }
+        // A missing target is never created: it is the unmounted drive's empty folder
+        _ => target.canonicalize().map_err(|_| {
+            format!(
+                "Target [{}] does not exist, its drive may not be mounted",
+                target.display()
+            )
+        })?,
+    };
+
+    match job.target_guard.as_str() {
+        GUARD_MOUNT if !mounts.iter().any(|mount| mount.mount_point == drive_root) => {
+            Err(format!(
+                "Target is not on a mounted drive ([{}] is not a mount point)",
+                drive_root.display()
+            ))
+        }
+        GUARD_MARKER if !drive_root.join(TARGET_MARKER).is_file() => Err(format!(
+            "Target has no {} marker in [{}]",
+            TARGET_MARKER,
+            drive_root.display()
+        )),
+        _ => Ok(()),
+    }
+}
+
+// Every mountinfo line reads "<id> <parent> <major:minor> <root> <mount point> ... - <fs> <device> ...",
+// with spaces & co. octal escaped
+pub fn get_mounts() -> Result<Vec<Mount>, String> {
+    let mountinfo = read_to_string(MOUNTINFO)
+        .map_err(|e| format!("Could not read {} because [{}]", MOUNTINFO, e))?;
+
+    Ok(mountinfo
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(' ').collect();
+            let separator = fields.iter().position(|field| *field == "-")?;
+
+            Some(Mount {
+                id: fields.first()?.parse().ok()?,
+                mount_point: PathBuf::from(unescape_mount_point(fields.get(4)?)),
+                device: unescape_mount_point(fields.get(separator + 2)?),
+            })
+        })
+        .collect())
+}
+
+// Tells two mounts apart: the kernel hands a freed mount id out again, but a drive plugged back
+// in gets a new device node (a network share has none, its id & source have to do)
+pub fn mount_key(mount: &Mount) -> String {
+    let node = metadata(&mount.device)
+        .map(|node| node.ino().to_string())
+        .unwrap_or_default();
+    format!("{} {} {}", mount.id, mount.device, node)
+}
+
+// `trigger` is either a mount point or a filesystem UUID (with or without "UUID=")
+pub fn find_trigger_mount<'a>(trigger: &str, mounts: &'a [Mount]) -> Option<&'a Mount> {
+    let trigger = trigger.trim();
+
+    if trigger.starts_with('/') || trigger.starts_with('~') {
+        let mount_point = normalise_path(trigger);
+        return mounts.iter().find(|mount| mount.mount_point == mount_point);
+    }
+
+    // The by-uuid link only exists while the drive is plugged in
+    let uuid = trigger.strip_prefix(UUID_PREFIX).unwrap_or(trigger);
+    let device = Path::new(DISK_BY_UUID).join(uuid).canonicalize().ok()?;
+
+    mounts
+        .iter()
+        .find(|mount| Path::new(&mount.device) == device)
+}
+
+fn unescape_mount_point(mount_point: &str) -> String {
+    let mut unescaped = String::with_capacity(mount_point.len());
+    let mut chars = mount_point.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        let code: String = chars.by_ref().take(3).collect();
+        match u8::from_str_radix(&code, 8) {
+            Ok(byte) => unescaped.push(byte as char),
+            Err(_) => {
+                unescaped.push(c);
+                unescaped.push_str(&code);
+            }
+        }
+    }
+
+    unescaped
+}
+
+// Returns the id of the inserted log, if any
 pub fn log_results(
     conn: &mut Connection,
     mut log: Log,
     success_directories: Vec<LogResult>,
     failed_directories: Vec<LogResult>,
-) {
+) -> Option<u16> {
     if success_directories.is_empty() && failed_directories.is_empty() {
-        return;
+        return None;
     }
 
-    // Set status
-    let status = match (
-        success_directories.is_empty(),
-        failed_directories.is_empty(),
-    ) {
-        (true, true) => SUCCESS,
-        (false, true) => SUCCESS,
-        (true, false) => FAILED,
-        _ => PARTIAL,
-    };
-    log.status = status.into();
+    // Set status, unless the run was interrupted and already says so
+    if log.status.is_empty() {
+        let status = match (
+            success_directories.is_empty(),
+            failed_directories.is_empty(),
+        ) {
+            (true, true) => SUCCESS,
+            (false, true) => SUCCESS,
+            (true, false) => FAILED,
+            _ => PARTIAL,
+        };
+        log.status = status.into();
+    }
 
     // Set counts
     let success_count = success_directories.len();
@@ -466,13 +821,68 @@ pub fn log_results(
             if let Err(error) = insert_log_resuts(conn, id as u16, &all_results) {
                 fallback_log(&log, &all_results, &error);
             }
+            Some(id as u16)
         }
         Err(error) => {
             fallback_log(&log, &all_results, &error);
+            None
         }
     }
 }
 
+// Queue the next attempt of every failed job that still has retries left
+pub fn schedule_retries(
+    conn: &mut Connection,
+    failed_ids: &[u16],
+    origin_log_id: Option<u16>,
+    attempt: u8,
+) {
+    for id in failed_ids {
+        let Some(job) = get_job(conn, *id) else {
+            continue;
+        };
+
+        if attempt >= job.retry_max {
+            continue;
+        }
+
+        let next_attempt = attempt + 1;
+        let Some(due_at) = Duration::try_minutes(job.retry_wait(next_attempt))
+            .and_then(|wait| Local::now().checked_add_signed(wait))
+        else {
+            eprintln!(
+                "❌ Could not schedule a retry of job [{}] because its wait is out of range",
+                id
+            );
+            continue;
+        };
+        let due_at = due_at.format(DUE_FORMAT).to_string();
+
+        let pending_run = PendingRun::new(*id, due_at, next_attempt, origin_log_id, RETRY);
+        match insert_pending_run(conn, &pending_run) {
+            Ok(_) => println!(
+                "🔁 Job [{}] will be retried at {} (attempt {} of {})",
+                id, pending_run.due_at, next_attempt, job.retry_max
+            ),
+            Err(error) => eprintln!("{}", error),
+        }
+    }
+}
+
+// Keeps a single deferred run per job, however many of its slots a block swallows
+pub fn defer_run(conn: &mut Connection, job_id: u16, until: &NaiveDateTime) {
+    if has_pending_run(conn, job_id, DEFERRED) {
+        return;
+    }
+
+    let due_at = until.format(DUE_FORMAT).to_string();
+    let pending_run = PendingRun::new(job_id, due_at, 0, None, DEFERRED);
+    match insert_pending_run(conn, &pending_run) {
+        Ok(_) => println!("⏸️ Job [{}] deferred to {}", job_id, pending_run.due_at),
+        Err(error) => eprintln!("{}", error),
+    }
+}
+
 // Dissect content into lines of 5 words max or 32 characters max
 pub fn into_lines(content: &str) -> (u16, String) {
     let words: Vec<&str> = content.split_whitespace().collect();
@@ -510,30 +920,13 @@ pub fn into_lines(content: &str) -> (u16, String) {
 }
 
 pub fn get_active_jobs<'a>(search_term: &str, filter: &Filter, jobs: &'a [Job]) -> Vec<&'a Job> {
-    let jobs = if search_term.is_empty() {
-        jobs.iter()
-            .filter(|job| match filter {
-                Filter::All => true,
-                Filter::Active => job.active == 1,
-                Filter::Inactive => job.active == 0,
-            })
-            .collect()
-    } else {
-        jobs.iter()
-            .filter(|job| {
-                job.source.to_lowercase().contains(search_term)
-                    || job.target.to_lowercase().contains(search_term)
-                    || job.id.unwrap().to_string().contains(search_term)
-            })
-            .filter(|job| match filter {
-                Filter::All => true,
-                Filter::Active => job.active == 1,
-                Filter::Inactive => job.active == 0,
-            })
-            .collect()
+    let query = JobQuery {
+        active: filter.active(),
+        search: Some(search_term.to_string()),
+        ..Default::default()
     };
 
-    jobs
+    jobs.iter().filter(|job| query.matches(job)).collect()
 }
 
 pub fn get_active_logs<'a>(search_term: &str, logs: &'a [Log]) -> Vec<&'a Log> {
@@ -560,4 +953,15 @@ pub fn status_emoji(value: u8) -> String {
         1 => EMOJI_ACTIVE.to_string(),
         _ => EMOJI_INACTIVE.to_string(),
     }
+}
+
+pub fn log_status_emoji(status: &str) -> &'static str {
+    match status {
+        SUCCESS => EMOJI_STATUS_SUCCESS,
+        FAILED => EMOJI_STATUS_FAILED,
+        PARTIAL => EMOJI_STATUS_PARTIAL,
+        CANCELLED => EMOJI_STATUS_CANCELLED,
+        TIMED_OUT => EMOJI_STATUS_TIMED_OUT,
+        _ => EMOJI_STATUS_OTHER,
+    }
 }
\ No newline at end of file
@@ -1,35 +1,105 @@
 // Standards ─────────────────────────────────────────────────────
 use std::{
-    collections::HashMap,
-    fs::{remove_dir_all, remove_file},
-    path::Path,
-    sync::{Arc, Mutex, mpsc::channel},
+    collections::{HashMap, HashSet},
+    env,
+    fs::{read_dir, read_to_string, remove_file},
+    os::unix::net::{UnixListener, UnixStream},
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        Arc, LazyLock, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{Receiver, RecvTimeoutError, Sender, channel},
+    },
+    thread::sleep,
+    time::{Duration, Instant},
 };
 
 // Crates ───────────────────────────────────────────────────────
-use chrono::{DateTime, Datelike, Local, Timelike};
+use chrono::Local;
 use notify::{
-    Event,
-    EventKind::{Create, Modify},
-    RecommendedWatcher, RecursiveMode, Watcher,
+    Event, EventKind,
+    EventKind::{Access, Create, Modify, Remove},
+    PollWatcher, RecommendedWatcher, RecursiveMode, Watcher,
     event::{
-        CreateKind::{File, Folder},
-        DataChange::Any,
-        ModifyKind::{Data, Name},
-        RenameMode::{From, To},
+        AccessKind, CreateKind, DataChange, MetadataKind, ModifyKind,
+        ModifyKind::{Data, Metadata, Name},
+        RemoveKind, RenameMode,
+        RenameMode::{Both, From, To},
     },
 };
+use signal_hook::{
+    consts::{SIGINT, SIGTERM},
+    iterator::Signals,
+};
 
 // mods ──────────────────────────────────────────────────────────
 use syncrab::{
-    consts::{ACTIVE, FAILED, REAL_TIME},
-    db::db::{db_path, get_jobs_to_run, init_db, insert_log, insert_log_resuts},
-    structs::{Job, Log, LogResult, WatchedJob},
-    utils::{are_paths_valid, copy_dir, fallback_log, log_results, normalise_path},
+    consts::{
+        CONTROL_TIMEOUT_MS, CTL, DEBOUNCE_MAX_MS, DEBOUNCE_MS, DEBUG_VAR, HEARTBEAT_FORMAT,
+        HEARTBEAT_SECS, LOG_STAMP_FORMAT, MAX_USER_INSTANCES, MAX_USER_WATCHES, NOTE_PREFIX,
+        REAL_TIME, RESCAN_INTERVAL_SECS, SHUTDOWN_CANCEL_SECS, SHUTDOWN_GRACE_SECS, WATCH_FAILED,
+        WATCH_NATIVE, WATCH_POLLING, WATCH_WORKERS, WATCHING,
+    },
+    control::{Request, Response, read_message, run_ctl, socket_path, write_message},
+    db::{
+        db::{
+            db_path, delete_heartbeat, delete_watch_states, find_jobs, get_policies,
+            get_watch_states, init_db, set_heartbeat, set_watch_state,
+        },
+        query::JobQuery,
+    },
+    lock::{CancelToken, JobLock, LockError, request_cancel},
+    retention::prune_if_due,
+    structs::{Heartbeat, Job, Log, LogResult, WatchState, WatchStatus, WatchedJob},
+    utils::{
+        are_paths_valid, blocked_until, check_target_guard, copy_dir, defer_run, log_results,
+        normalise_path, remove_from_target, rename_in_target, sync_metadata,
+    },
 };
 
+type Watchers = Arc<Mutex<Hub>>;
+type Statuses = Arc<Mutex<HashMap<u16, WatchStatus>>>;
+
+// The watched jobs & the native watch they share
+struct Hub {
+    jobs: HashMap<u16, WatchedJob>,
+    // Why it could not be created, native jobs are then polled
+    native: Result<SharedWatch, String>,
+    tx: Sender<Message>,
+}
+
+// Everything the dispatcher reacts to, in arrival order
+enum Message {
+    // From the shared native watch (None) or from the watch polling the given job
+    Fs(Option<u16>, notify::Result<Event>),
+    // The job starts being watched, reconciled first when there is a reason to
+    Watch {
+        job: Box<Job>,
+        source: PathBuf,
+        native: bool,
+        reconcile: Option<String>,
+    },
+    // What the job collected is synced one last time, then `ack` gets its id
+    Unwatch(u16, Option<Sender<u16>>),
+    // Asked over the control socket
+    Resync(u16),
+    Wake,
+    // A worker is done with a queue's sync, and tells how many paths failed
+    Done(u64, Option<usize>),
+}
+
 // Init ──────────────────────────────────────────────────────────
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == CTL) {
+        if let Err(error) = run_ctl(&args[1..]) {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+        return;
+    }
+
     let db_path = db_path();
     let db_path = db_path.as_path();
 
@@ -52,160 +122,1163 @@ fn main() {
         return;
     }
 
-    let active_watchers: Arc<Mutex<HashMap<u16, WatchedJob>>> =
-        Arc::new(Mutex::new(HashMap::new()));
+    let statuses: Statuses = Arc::new(Mutex::new(HashMap::new()));
 
-    handle_db_event(None, Arc::clone(&active_watchers));
+    // A second watcher would sync every job twice
+    let socket = socket_path();
+    if UnixStream::connect(&socket).is_ok() {
+        eprintln!(
+            "❌ Another syncrab_w is already running, it answers at [{}]",
+            socket.display()
+        );
+        return;
+    }
+
+    // One native watch & one dispatcher for all the jobs
+    let (hub_tx, hub_rx) = channel();
+    let native = SharedWatch::new(hub_tx.clone());
+    if let Err(error) = &native {
+        eprintln!("❌ {}", error);
+    }
+    let active_watchers: Watchers = Arc::new(Mutex::new(Hub {
+        jobs: HashMap::new(),
+        native,
+        tx: hub_tx.clone(),
+    }));
+
+    let dispatch_statuses = Arc::clone(&statuses);
+    std::thread::spawn(move || dispatch(hub_rx, hub_tx, dispatch_statuses));
+
+    let _ = remove_file(&socket); // left behind by a watcher that died
+    match UnixListener::bind(&socket) {
+        Ok(listener) => {
+            let watchers = Arc::clone(&active_watchers);
+            let statuses = Arc::clone(&statuses);
+            std::thread::spawn(move || serve_control(listener, watchers, statuses));
+        }
+        Err(e) => eprintln!(
+            "❌ Failed to open the control socket [{}] because [{}]",
+            socket.display(),
+            e
+        ),
+    }
+
+    // The states of a previous run say nothing about this one
+    if let Err(error) = delete_watch_states(&mut init_db(), None) {
+        eprintln!("{}", error);
+    }
+
+    let beat_statuses = Arc::clone(&statuses);
+    std::thread::spawn(move || beat(beat_statuses));
+
+    let signal_watchers = Arc::clone(&active_watchers);
+    std::thread::spawn(move || handle_signals(signal_watchers, socket));
+
+    handle_db_event(None, Arc::clone(&active_watchers), Arc::clone(&statuses));
 
     for res in rx {
         match res {
-            Ok(event) => handle_db_event(Some(event), Arc::clone(&active_watchers)),
+            Ok(event) => handle_db_event(
+                Some(event),
+                Arc::clone(&active_watchers),
+                Arc::clone(&statuses),
+            ),
             Err(e) => eprintln!("❌ Error occured while watching the DB: [{}]", e),
         }
     }
 }
 
-fn handle_db_event(event: Option<Event>, active_watchers: Arc<Mutex<HashMap<u16, WatchedJob>>>) {
+// Tells whoever reads the DB that syncrab_w is alive, every HEARTBEAT_SECS
+fn beat(statuses: Statuses) {
+    let mut conn = init_db();
+    let started_at = Local::now().format(HEARTBEAT_FORMAT).to_string();
+
+    while !SHUTTING_DOWN.load(Ordering::SeqCst) {
+        let (watched_count, last_event_at) = {
+            let statuses = statuses.lock().unwrap();
+            let last_event_at = statuses
+                .values()
+                .filter_map(|status| status.last_event.clone())
+                .max();
+            (statuses.len() as u16, last_event_at)
+        };
+
+        let heartbeat = Heartbeat {
+            pid: process::id(),
+            started_at: started_at.to_string(),
+            beat_at: Local::now().format(HEARTBEAT_FORMAT).to_string(),
+            watched_count,
+            last_event_at,
+        };
+        if let Err(error) = set_heartbeat(&mut conn, &heartbeat) {
+            eprintln!("{}", error);
+        }
+
+        // Its syncs add to the journal the most
+        prune_if_due(&mut conn, &Local::now().naive_local());
+
+        sleep(Duration::from_secs(HEARTBEAT_SECS));
+    }
+}
+
+fn handle_db_event(event: Option<Event>, active_watchers: Watchers, statuses: Statuses) {
+    if SHUTTING_DOWN.load(Ordering::SeqCst) {
+        return;
+    }
+
     if let Some(event) = event {
         if !event.kind.is_modify() {
             return;
         }
     }
 
-    let now: DateTime<Local> = Local::now();
-
     let mut conn = init_db();
-    let jobs: Vec<Job> = get_jobs_to_run(
-        &conn,
-        Some((REAL_TIME.to_owned(), Some(ACTIVE.to_owned()))),
-        now.weekday().to_string(),
-        now.hour() as u8,
-    )
-    .get(REAL_TIME)
-    .unwrap()
-    .clone();
+    let query = JobQuery {
+        frequencies: vec![REAL_TIME.into()],
+        active: Some(true),
+        ..Default::default()
+    };
+    let jobs: Vec<Job> = find_jobs(&conn, &query);
 
     let job_ids: Vec<u16> = jobs.iter().map(|job| job.id.unwrap()).collect();
-    let mut watchers = active_watchers.lock().unwrap();
-    let inactive_ids: Vec<u16> = watchers
+    let mut hub = active_watchers.lock().unwrap();
+    let hub = &mut *hub;
+    let inactive_ids: Vec<u16> = hub
+        .jobs
         .keys()
         .filter(|id| !job_ids.contains(id))
         .cloned()
         .collect();
 
+    let log = Log::new();
+    let mut success_directories: Vec<LogResult> = Vec::new();
     let mut failed_directories: Vec<LogResult> = Vec::new();
 
     // job no longer exists or inactive in the DB
     for id in inactive_ids {
-        if let Some(watched_job) = watchers.remove(&id) {
-            let WatchedJob {
-                mut job_watcher,
-                job,
-                job_tx,
-            } = watched_job;
-            let _ = job_watcher.unwatch(&Path::new(&job.source)); // cleanly stop watching
-            drop(job_tx); // drop the sender
+        if let Some(watched_job) = hub.jobs.remove(&id) {
+            stop_watching(&hub.tx, watched_job, None);
         }
+        statuses.lock().unwrap().remove(&id);
     }
 
-    if jobs.is_empty() {
-        return;
+    // Why each job (re)starting to be watched must be reconciled first, if it must
+    let mut reconcile_reasons: HashMap<u16, Option<String>> = HashMap::new();
+
+    // job edited in the DB: its watch is rebuilt below
+    for job in &jobs {
+        let id = job.id.unwrap();
+        let Some(watched_job) = hub.jobs.get(&id) else {
+            continue;
+        };
+        if watched_job.job == *job {
+            continue;
+        }
+
+        let changed = changed_fields(&watched_job.job, job);
+        let reason = if changed.is_empty() {
+            "its settings changed".to_string()
+        } else {
+            format!("its {} changed", changed.join(" & "))
+        };
+        println!("♻️ Reloading job [{}] because {}", id, reason);
+        success_directories.push(LogResult::new(
+            REAL_TIME,
+            &format!("{} Reloaded because {}", NOTE_PREFIX, reason),
+            job,
+        ));
+
+        // Only new paths leave the target out of date
+        let paths_changed = changed.contains(&"source") || changed.contains(&"target");
+        reconcile_reasons.insert(id, paths_changed.then_some(reason));
+
+        if let Some(watched_job) = hub.jobs.remove(&id) {
+            stop_watching(&hub.tx, watched_job, None);
+        }
+    }
+
+    // The native watch drops the sources no job needs anymore. Jobs whose source it cannot
+    // watch again are watched anew below, polled if it keeps failing
+    let native_sources: Vec<PathBuf> = hub
+        .jobs
+        .values()
+        .filter(|watched_job| watched_job.poll_watcher.is_none())
+        .map(|watched_job| watched_job.source.clone())
+        .chain(
+            jobs.iter()
+                .filter(|job| {
+                    job.watch_backend != WATCH_POLLING && !hub.jobs.contains_key(&job.id.unwrap())
+                })
+                .map(|job| normalise_path(&job.source)),
+        )
+        .collect();
+    if let Ok(shared) = &mut hub.native {
+        for lost in shared.retain(&native_sources) {
+            let lost_ids: Vec<u16> = hub
+                .jobs
+                .iter()
+                .filter(|(_, watched_job)| {
+                    watched_job.poll_watcher.is_none() && watched_job.source.starts_with(&lost)
+                })
+                .map(|(id, _)| *id)
+                .collect();
+
+            for id in lost_ids {
+                if let Some(watched_job) = hub.jobs.remove(&id) {
+                    stop_watching(&hub.tx, watched_job, None);
+                }
+                reconcile_reasons.insert(id, Some("its native watch was lost".into()));
+            }
+        }
+    }
+
+    // Jobs that are no longer active have no watch state
+    let watch_states = get_watch_states(&conn);
+    for id in watch_states.keys().filter(|id| !job_ids.contains(id)) {
+        if let Err(error) = delete_watch_states(&mut conn, Some(*id)) {
+            eprintln!("{}", error);
+        }
     }
 
     for job in jobs {
         // already watching the job
-        if watchers.contains_key(&job.id.unwrap()) {
+        if hub.jobs.contains_key(&job.id.unwrap()) {
             continue;
         }
 
         let source = normalise_path(&job.source);
         let target = normalise_path(&job.target);
 
-        if !are_paths_valid(
+        let mut invalid_paths: Vec<LogResult> = Vec::new();
+        let watched = if are_paths_valid(
             &REAL_TIME.into(),
             &job,
             &source,
             &target,
-            &mut failed_directories,
+            &mut invalid_paths,
         ) {
-            continue;
-        }
+            watch_source(hub, &job, &source)
+        } else {
+            Err(invalid_paths
+                .pop()
+                .map(|result| result.message)
+                .unwrap_or_default())
+        };
 
-        let (job_tx, job_rx) = channel();
+        let state = WatchState {
+            job_id: job.id.unwrap(),
+            state: WATCHING.into(),
+            error: None,
+        };
 
-        let mut job_watcher =
-            match RecommendedWatcher::new(job_tx.clone(), notify::Config::default()) {
-                Ok(w) => w,
-                Err(e) => {
-                    failed_directories.push(LogResult::new(
-                        REAL_TIME.into(),
-                        &format!("Failed to create file watcher because [{}]", e),
-                        &job.source,
-                        &job.target,
-                    ));
-                    continue;
+        let (poll_watcher, fallback) = match watched {
+            Ok(watched) => watched,
+            Err(error) => {
+                let state = WatchState {
+                    state: WATCH_FAILED.into(),
+                    error: Some(error.clone()),
+                    ..state
+                };
+
+                // Journaled once, not on every change of the DB while it keeps failing
+                if watch_states.get(&state.job_id) != Some(&state) {
+                    failed_directories.push(LogResult::new(REAL_TIME, &error, &job));
+                    if let Err(error) = set_watch_state(&mut conn, &state) {
+                        eprintln!("{}", error);
+                    }
                 }
-            };
+                continue;
+            }
+        };
 
-        if let Err(e) = job_watcher.watch(source.as_path(), RecursiveMode::Recursive) {
-            failed_directories.push(LogResult::new(
-                REAL_TIME.into(),
-                &format!("Failed to create file watcher because [{}]", e),
-                &job.source,
-                &job.target,
+        // Journaled once as well, the job still gets synced
+        let state = WatchState {
+            error: fallback,
+            ..state
+        };
+        if let Some(error) = &state.error
+            && watch_states.get(&state.job_id) != Some(&state)
+        {
+            println!("🔁 Polling job [{}] because {}", state.job_id, error);
+            success_directories.push(LogResult::new(
+                REAL_TIME,
+                &format!(
+                    "{} Polling every {}s because {}",
+                    NOTE_PREFIX, job.poll_interval, error
+                ),
+                &job,
             ));
-            continue;
         }
 
-        watchers.insert(
+        if let Err(error) = set_watch_state(&mut conn, &state) {
+            eprintln!("{}", error);
+        }
+
+        let native = poll_watcher.is_none();
+        let backend = if native { WATCH_NATIVE } else { WATCH_POLLING };
+
+        // A reloaded job stays paused & keeps its counts
+        statuses
+            .lock()
+            .unwrap()
+            .entry(job.id.unwrap())
+            .and_modify(|status| {
+                status.source = job.source.to_string();
+                status.target = job.target.to_string();
+            })
+            .or_insert_with(|| WatchStatus::new(&job))
+            .backend = backend.into();
+
+        // Hand the job over to the dispatcher
+        let reason = reconcile_reasons
+            .remove(&job.id.unwrap())
+            .unwrap_or_else(|| Some("it started being watched".into()));
+        let _ = hub.tx.send(Message::Watch {
+            job: Box::new(job.clone()),
+            source: source.clone(),
+            native,
+            reconcile: reason,
+        });
+
+        hub.jobs.insert(
             job.id.unwrap(),
             WatchedJob {
-                job: job.clone(),
-                job_watcher,
-                job_tx,
+                job,
+                source,
+                poll_watcher,
             },
         );
+    }
 
-        // Spawn a thread for this job watcher
-        let job_clone = job.clone();
-        std::thread::spawn(move || {
-            for res in job_rx {
-                match res {
-                    Ok(event) => sync_file_event(&event, &job_clone),
-                    Err(e) => eprintln!("Job watch error: {}\n\n", e),
+    log_results(&mut conn, log, success_directories, failed_directories);
+}
+
+// Watches the source with the job's backend: native jobs share one watch, polled ones get their
+// own. A native watch that fails falls back to polling, why comes along to be journaled
+fn watch_source(
+    hub: &mut Hub,
+    job: &Job,
+    source: &Path,
+) -> Result<(Option<PollWatcher>, Option<String>), String> {
+    if job.watch_backend == WATCH_POLLING {
+        return Ok((Some(poll_source(job, source, hub.tx.clone())?), None));
+    }
+
+    let native_error = match &mut hub.native {
+        Ok(shared) => match shared.add(source) {
+            Ok(()) => return Ok((None, None)),
+            Err(error) => error,
+        },
+        Err(error) => error.clone(),
+    };
+
+    let poll_watcher = poll_source(job, source, hub.tx.clone())
+        .map_err(|e| format!("{}, then {}", native_error, e))?;
+    let fallback = format!("the native watch failed: {}", native_error);
+    Ok((Some(poll_watcher), Some(fallback)))
+}
+
+fn poll_source(job: &Job, source: &Path, tx: Sender<Message>) -> Result<PollWatcher, String> {
+    let job_id = job.id.unwrap();
+    let config = notify::Config::default()
+        .with_poll_interval(Duration::from_secs(job.poll_interval.max(1) as u64));
+
+    let mut poll_watcher = PollWatcher::new(
+        move |event| {
+            let _ = tx.send(Message::Fs(Some(job_id), event));
+        },
+        config,
+    )
+    .map_err(|e| format!("Failed to create file watcher because [{}]", e))?;
+
+    poll_watcher
+        .watch(source, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch [{}] because [{}]", source.display(), e))?;
+
+    Ok(poll_watcher)
+}
+
+// The dispatcher syncs what the job already collected, then forgets it and sends `ack` its id
+fn stop_watching(tx: &Sender<Message>, watched_job: WatchedJob, ack: Option<Sender<u16>>) -> Job {
+    let WatchedJob {
+        job, poll_watcher, ..
+    } = watched_job;
+    drop(poll_watcher); // stop polling
+    let _ = tx.send(Message::Unwatch(job.id.unwrap(), ack));
+    job
+}
+
+// Shared watch ──────────────────────────────────────────────────
+
+// One recursive native watch per outermost source: a source inside another one is already
+// covered, its events reach every job whose source contains their path
+struct SharedWatch {
+    watcher: RecommendedWatcher,
+    roots: Vec<PathBuf>,
+}
+
+impl SharedWatch {
+    fn new(tx: Sender<Message>) -> Result<Self, String> {
+        let watcher = RecommendedWatcher::new(
+            move |event| {
+                let _ = tx.send(Message::Fs(None, event));
+            },
+            notify::Config::default(),
+        )
+        .map_err(|e| {
+            format!(
+                "Failed to create the shared file watcher because [{}], {} may be too low",
+                e, MAX_USER_INSTANCES
+            )
+        })?;
+
+        Ok(Self {
+            watcher,
+            roots: Vec::new(),
+        })
+    }
+
+    fn add(&mut self, source: &Path) -> Result<(), String> {
+        if self.roots.iter().any(|root| source.starts_with(root)) {
+            return Ok(());
+        }
+
+        // The new root covers them, they are watched again if it cannot be
+        let inner: Vec<PathBuf> = self
+            .roots
+            .iter()
+            .filter(|root| root.starts_with(source))
+            .cloned()
+            .collect();
+        for root in &inner {
+            let _ = self.watcher.unwatch(root);
+        }
+
+        match self.watcher.watch(source, RecursiveMode::Recursive) {
+            Ok(()) => {
+                self.roots.retain(|root| !root.starts_with(source));
+                self.roots.push(source.to_path_buf());
+                Ok(())
+            }
+            Err(e) => {
+                for root in &inner {
+                    let _ = self.watcher.watch(root, RecursiveMode::Recursive);
                 }
+                Err(describe_watch_error(&e, source))
             }
-        });
+        }
     }
 
-    if failed_directories.is_empty() {
-        return;
+    // Only keeps watching what `sources` need, returns those it could not watch again
+    fn retain(&mut self, sources: &[PathBuf]) -> Vec<PathBuf> {
+        let needed: Vec<PathBuf> = sources
+            .iter()
+            .filter(|source| {
+                !sources
+                    .iter()
+                    .any(|other| other != *source && source.starts_with(other))
+            })
+            .cloned()
+            .collect();
+
+        let dropped: Vec<PathBuf> = self
+            .roots
+            .iter()
+            .filter(|root| !needed.contains(root))
+            .cloned()
+            .collect();
+        for root in &dropped {
+            let _ = self.watcher.unwatch(root);
+        }
+        self.roots.retain(|root| needed.contains(root));
+
+        // Were covered by a root that just went away, new sources are left to `add`
+        let mut lost = Vec::new();
+        for source in needed {
+            if self.roots.contains(&source) || !dropped.iter().any(|root| source.starts_with(root))
+            {
+                continue;
+            }
+            match self.watcher.watch(&source, RecursiveMode::Recursive) {
+                Ok(()) => self.roots.push(source),
+                Err(e) => {
+                    eprintln!("❌ {}", describe_watch_error(&e, &source));
+                    lost.push(source);
+                }
+            }
+        }
+        lost
     }
+}
 
-    let log = Log {
-        id: None,
-        startstamp: now.format("%d-%m-%Y %H:%M").to_string(),
-        status: FAILED.into(),
-        success_count: 0,
-        failed_count: failed_directories.len() as u16,
-        endstamp: Local::now().format("%d-%m-%Y %H:%M").to_string(),
-        log_results: None,
-    };
-    match insert_log(&mut conn, log.clone()) {
-        Ok(id) => {
-            if let Err(error) = insert_log_resuts(&mut conn, id as u16, &failed_directories) {
-                fallback_log(&log, &failed_directories, &error);
+// The inotify watch limit is why a native watch usually fails: say how far off it is
+fn describe_watch_error(error: &notify::Error, source: &Path) -> String {
+    match error.kind {
+        notify::ErrorKind::MaxFilesWatch => format!(
+            "Failed to watch [{}] because the inotify watch limit is reached: it needs {} watches (one per folder) and {} is {}. Raise it (e.g. `sudo sysctl fs.inotify.max_user_watches=524288`) or poll this job",
+            source.display(),
+            count_folders(source),
+            MAX_USER_WATCHES,
+            read_to_string(MAX_USER_WATCHES)
+                .map(|limit| limit.trim().to_string())
+                .unwrap_or_else(|_| "unknown".into())
+        ),
+        _ => format!("Failed to watch [{}] because [{}]", source.display(), error),
+    }
+}
+
+fn count_folders(path: &Path) -> usize {
+    let children: usize = read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+                .map(|entry| count_folders(&entry.path()))
+                .sum()
+        })
+        .unwrap_or_default();
+    children + 1
+}
+
+// The edits the watcher cares about, by name, for the journal
+fn changed_fields(old: &Job, new: &Job) -> Vec<&'static str> {
+    [
+        ("source", old.source != new.source),
+        ("target", old.target != new.target),
+        ("mirror", old.mirror != new.mirror),
+        ("guard", old.target_guard != new.target_guard),
+        ("watch backend", old.watch_backend != new.watch_backend),
+        ("poll interval", old.poll_interval != new.poll_interval),
+    ]
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(field, _)| field)
+    .collect()
+}
+
+// What a settled path asks of the target
+#[derive(Debug, Clone, PartialEq)]
+enum SyncAction {
+    Copy,
+    Delete,
+    // Was renamed from that source path: its copy in the target is renamed too
+    Rename(PathBuf),
+    // Only its permissions or times changed: the target's copy gets them, nothing is copied
+    Metadata,
+    // Unclear what happened to it: copied if it still exists when synced, deleted otherwise
+    Refresh,
+}
+
+// Every kind notify knows of is listed: one it adds fails the build instead of going unsynced.
+// None: nothing to sync
+fn action_of(kind: &EventKind) -> Option<SyncAction> {
+    match kind {
+        // Sync into target (create/update/move in)
+        // Polling does not tell files from folders, and sees writes as a newer mtime
+        Create(CreateKind::Any | CreateKind::File | CreateKind::Folder | CreateKind::Other)
+        | Modify(ModifyKind::Any)
+        | Modify(Data(
+            DataChange::Any | DataChange::Size | DataChange::Content | DataChange::Other,
+        ))
+        | Modify(Metadata(MetadataKind::WriteTime))
+        | Modify(Name(To)) => Some(SyncAction::Copy),
+        // Update the target's copy (chmod/chown/touch)
+        Modify(Metadata(
+            MetadataKind::Any
+            | MetadataKind::Permissions
+            | MetadataKind::Ownership
+            | MetadataKind::Extended
+            | MetadataKind::Other,
+        )) => Some(SyncAction::Metadata),
+        // Delete from target (delete/move out), mirrored jobs only
+        Remove(RemoveKind::Any | RemoveKind::File | RemoveKind::Folder | RemoveKind::Other)
+        | Modify(Name(From)) => Some(SyncAction::Delete),
+        // A `Both` that could not be paired, or a rename that does not tell which half it is
+        EventKind::Any
+        | Modify(ModifyKind::Other)
+        | Modify(Name(Both | RenameMode::Any | RenameMode::Other)) => Some(SyncAction::Refresh),
+        // Reads, opens & closes change nothing
+        Access(
+            AccessKind::Any
+            | AccessKind::Read
+            | AccessKind::Open(_)
+            | AccessKind::Close(_)
+            | AccessKind::Other,
+        )
+        | Modify(Metadata(MetadataKind::AccessTime))
+        | EventKind::Other => None,
+    }
+}
+
+static DEBUG: LazyLock<bool> = LazyLock::new(|| env::var_os(DEBUG_VAR).is_some());
+
+// Only logged when DEBUG_VAR is set
+fn debug(message: &str) {
+    if *DEBUG {
+        println!("🐞 {}", message);
+    }
+}
+
+// Dispatcher ────────────────────────────────────────────────────
+
+// A watched job's pending changes
+struct JobQueue {
+    job_id: u16,
+    job: Job,
+    source: PathBuf,
+    // Gets the shared native watch's events, otherwise only those of its own polling
+    native: bool,
+    // The latest action wins: a file created then moved out only gets deleted
+    batch: HashMap<PathBuf, SyncAction>,
+    // Rename halves waiting for their other half, by tracker: the path it left & what the batch
+    // held for it. Deleted when unmatched by the flush
+    renamed_from: HashMap<usize, (PathBuf, Option<SyncAction>)>,
+    // Already paired: the rename's `Both` event adds nothing
+    paired: HashSet<usize>,
+    // When the window opened & when its last event came in
+    window: Option<(Instant, Instant)>,
+    // Why the target may have drifted: the next flush reconciles instead of syncing the batch
+    rescan: Option<String>,
+    next_rescan: Instant,
+    // A worker is syncing it: what comes in meanwhile waits for the next flush
+    busy: bool,
+    // Unwatched: forgotten once what it collected is synced, then `ack` gets its id
+    stopping: bool,
+    ack: Option<Sender<u16>>,
+}
+
+impl JobQueue {
+    fn new(job: Job, source: PathBuf, native: bool, reconcile: Option<String>) -> Self {
+        Self {
+            job_id: job.id.unwrap(),
+            job,
+            source,
+            native,
+            batch: HashMap::new(),
+            renamed_from: HashMap::new(),
+            paired: HashSet::new(),
+            window: None,
+            rescan: reconcile,
+            next_rescan: Instant::now() + Duration::from_secs(RESCAN_INTERVAL_SECS),
+            busy: false,
+            stopping: false,
+            ack: None,
+        }
+    }
+
+    fn wants(&self, from: Option<u16>) -> bool {
+        !self.stopping
+            && match from {
+                Some(job_id) => !self.native && self.job_id == job_id,
+                None => self.native,
+            }
+    }
+
+    fn collect(&mut self, event: &notify::Result<Event>, statuses: &Statuses) {
+        let touched = match event {
+            // The kernel queue overflowed: events were dropped
+            Ok(event) if event.need_rescan() => {
+                self.rescan = Some("events were dropped".into());
+                true
+            }
+            Ok(event) if let Some(touched) = self.collect_rename(event) => touched,
+            Ok(event) => {
+                let Some(action) = action_of(&event.kind) else {
+                    return;
+                };
+                // The shared watch also reports paths of the other jobs
+                let paths: Vec<&PathBuf> = event
+                    .paths
+                    .iter()
+                    .filter(|path| path.starts_with(&self.source))
+                    .collect();
+                for path in &paths {
+                    self.record(path.to_path_buf(), action.clone());
+                }
+                !paths.is_empty()
+            }
+            Err(e) => {
+                if !e.paths.is_empty()
+                    && !e
+                        .paths
+                        .iter()
+                        .any(|path| path.starts_with(&self.source) || self.source.starts_with(path))
+                {
+                    return;
+                }
+                eprintln!("❌ Job [{}] watch error: {}", self.job_id, e);
+                self.rescan = Some(format!("the watch failed with [{}]", e));
+                true
             }
+        };
+
+        if touched {
+            let now = Instant::now();
+            self.window = Some((self.window.map_or(now, |(opened, _)| opened), now));
+            update_status(statuses, self.job_id, |status| {
+                status.last_event = Some(Local::now().format(HEARTBEAT_FORMAT).to_string())
+            });
+        }
+    }
+
+    // Pairs a rename's halves by their tracker, or takes both paths from its `Both` event. Moved in
+    // from outside the source is a copy, moved out of it a delete. Tells whether it concerned the
+    // job, None when there is nothing to pair: it is collected like any other event
+    fn collect_rename(&mut self, event: &Event) -> Option<bool> {
+        let source = self.source.clone();
+        let inside = |path: &PathBuf| path.starts_with(&source);
+
+        match (event.kind, event.tracker(), event.paths.as_slice()) {
+            (Modify(Name(From)), Some(tracker), [from]) if inside(from) => {
+                let prior = self.batch.remove(from);
+                self.renamed_from.insert(tracker, (from.clone(), prior));
+            }
+            (Modify(Name(To)), Some(tracker), [to]) => match self.renamed_from.remove(&tracker) {
+                Some((from, prior)) => {
+                    if inside(to) {
+                        self.pair(from, prior, to.clone());
+                    } else {
+                        self.unmatched(from, prior);
+                    }
+                    self.paired.insert(tracker);
+                }
+                None if inside(to) => self.record(to.clone(), SyncAction::Copy),
+                None => return Some(false),
+            },
+            (Modify(Name(Both)), tracker, _)
+                if tracker.is_some_and(|tracker| self.paired.remove(&tracker)) => {}
+            (Modify(Name(Both)), _, [from, to]) => match (inside(from), inside(to)) {
+                (true, true) => {
+                    let prior = self.batch.remove(from);
+                    self.pair(from.clone(), prior, to.clone());
+                }
+                (true, false) => self.record(from.clone(), SyncAction::Delete),
+                (false, true) => self.record(to.clone(), SyncAction::Copy),
+                (false, false) => return Some(false),
+            },
+            _ => return None,
         }
-        Err(error) => {
-            fallback_log(&log, &failed_directories, &error);
+        Some(true)
+    }
+
+    // A path renamed again keeps pointing at what the target holds: its first name
+    fn pair(&mut self, from: PathBuf, prior: Option<SyncAction>, to: PathBuf) {
+        let from = match prior {
+            Some(SyncAction::Rename(first)) => first,
+            _ => from,
+        };
+        self.record(to, SyncAction::Rename(from));
+    }
+
+    // Its other half never came, or landed outside the source: it left
+    fn unmatched(&mut self, from: PathBuf, prior: Option<SyncAction>) {
+        if let Some(SyncAction::Rename(first)) = prior {
+            self.batch.entry(first).or_insert(SyncAction::Delete);
         }
+        self.batch.entry(from).or_insert(SyncAction::Delete);
+    }
+
+    fn record(&mut self, path: PathBuf, action: SyncAction) {
+        match (self.batch.get(&path), &action) {
+            // Synced after its rename anyway
+            (Some(SyncAction::Rename(_)), SyncAction::Copy | SyncAction::Metadata) => return,
+            // A copy brings the metadata along
+            (Some(SyncAction::Copy | SyncAction::Refresh), SyncAction::Metadata) => return,
+            // What it was renamed from is left in the target
+            (Some(SyncAction::Rename(first)), _) => {
+                let first = first.clone();
+                self.batch.entry(first).or_insert(SyncAction::Delete);
+            }
+            _ => {}
+        }
+        self.batch.insert(path, action);
+    }
+
+    // The batch to sync, with the unmatched rename halves as deletes
+    fn take_batch(&mut self) -> HashMap<PathBuf, SyncAction> {
+        for (_, (from, prior)) in std::mem::take(&mut self.renamed_from) {
+            self.unmatched(from, prior);
+        }
+        self.paired.clear();
+        std::mem::take(&mut self.batch)
+    }
+
+    // When it should be flushed: a due rescan waits for the open window to settle
+    fn deadline(&self) -> Instant {
+        match self.window {
+            Some((opened, last_event)) => (last_event + Duration::from_millis(DEBOUNCE_MS))
+                .min(opened + Duration::from_millis(DEBOUNCE_MAX_MS)),
+            None if self.rescan.is_some() => Instant::now(),
+            None => self.next_rescan,
+        }
+    }
+
+    fn take_work(&mut self) -> Option<Work> {
+        if Instant::now() >= self.next_rescan {
+            self.rescan
+                .get_or_insert_with(|| "its periodic rescan was due".into());
+        }
+        self.window = None;
+
+        match self.rescan.take() {
+            // The reconciliation covers whatever the batch held
+            Some(reason) => {
+                self.batch.clear();
+                self.renamed_from.clear();
+                self.paired.clear();
+                self.next_rescan = Instant::now() + Duration::from_secs(RESCAN_INTERVAL_SECS);
+                Some(Work::Reconcile(reason))
+            }
+            None => Some(self.take_batch())
+                .filter(|batch| !batch.is_empty())
+                .map(Work::Batch),
+        }
+    }
+}
+
+// Routes the events to the jobs they concern and collects them per job until they settle for
+// DEBOUNCE_MS (or DEBOUNCE_MAX_MS went by), then hands each job's sync to the workers.
+// Lost events and RESCAN_INTERVAL_SECS trigger a reconciliation
+fn dispatch(rx: Receiver<Message>, tx: Sender<Message>, statuses: Statuses) {
+    let tasks = start_workers(WATCH_WORKERS, tx);
+    // By token: an edited job's old queue may still be syncing next to its new one
+    let mut queues: HashMap<u64, JobQueue> = HashMap::new();
+    let mut next_token: u64 = 0;
+
+    loop {
+        // Paused: only events & control requests wake the dispatcher up
+        let wait = queues
+            .values()
+            .filter(|queue| !queue.busy && !update_status(&statuses, queue.job_id, |_| {}))
+            .map(|queue| queue.deadline())
+            .min()
+            .map_or(Duration::MAX, |deadline| {
+                deadline.saturating_duration_since(Instant::now())
+            });
+
+        match rx.recv_timeout(wait) {
+            Ok(Message::Fs(_, Ok(event)))
+                if !event.need_rescan() && action_of(&event.kind).is_none() =>
+            {
+                debug(&format!(
+                    "Ignored {:?} on {:?}: nothing to sync",
+                    event.kind, event.paths
+                ));
+            }
+            Ok(Message::Fs(from, event)) => {
+                for queue in queues.values_mut().filter(|queue| queue.wants(from)) {
+                    queue.collect(&event, &statuses);
+                }
+            }
+            Ok(Message::Watch {
+                job,
+                source,
+                native,
+                reconcile,
+            }) => {
+                next_token += 1;
+                queues.insert(next_token, JobQueue::new(*job, source, native, reconcile));
+            }
+            Ok(Message::Unwatch(job_id, ack)) => {
+                for queue in queues
+                    .values_mut()
+                    .filter(|queue| queue.job_id == job_id && !queue.stopping)
+                {
+                    queue.stopping = true;
+                    queue.ack = ack.clone();
+                }
+            }
+            Ok(Message::Resync(job_id)) => {
+                for queue in queues
+                    .values_mut()
+                    .filter(|queue| queue.job_id == job_id && !queue.stopping)
+                {
+                    queue.rescan = Some("it was asked to resync".into());
+                }
+            }
+            Ok(Message::Done(token, failed)) => {
+                if let Some(queue) = queues.get_mut(&token) {
+                    queue.busy = false;
+                    record_sync(&statuses, queue.job_id, failed);
+                }
+            }
+            Ok(Message::Wake) | Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        let mut forgotten: Vec<u64> = Vec::new();
+        for (token, queue) in queues.iter_mut() {
+            // A stopping queue's status belongs to the job's new queue, if it has one
+            let is_paused = !queue.stopping
+                && update_status(&statuses, queue.job_id, |status| {
+                    status.queued = queue.batch.len()
+                });
+            if queue.busy || is_paused {
+                continue;
+            }
+
+            let work = if queue.stopping {
+                let batch = queue.take_batch();
+                if batch.is_empty() {
+                    forgotten.push(*token);
+                    continue;
+                }
+                Work::Batch(batch)
+            } else if queue.deadline() <= Instant::now()
+                && let Some(work) = queue.take_work()
+            {
+                work
+            } else {
+                continue;
+            };
+
+            queue.busy = true;
+            let _ = tasks.send(Task {
+                token: *token,
+                job: queue.job.clone(),
+                work,
+            });
+        }
+
+        for token in forgotten {
+            if let Some(queue) = queues.remove(&token)
+                && let Some(ack) = queue.ack
+            {
+                let _ = ack.send(queue.job_id);
+            }
+        }
+    }
+}
+
+// Applies `update` to the job's status if it is still watched, and tells whether it is paused
+fn update_status(statuses: &Statuses, job_id: u16, update: impl FnOnce(&mut WatchStatus)) -> bool {
+    match statuses.lock().unwrap().get_mut(&job_id) {
+        Some(status) => {
+            update(status);
+            status.paused
+        }
+        None => false,
     }
 }
 
-fn sync_file_event(event: &Event, job: &Job) {
-    if !(event.kind.is_modify() || event.kind.is_create()) {
+// `failed`: how many paths a sync failed on, None when it did not run
+fn record_sync(statuses: &Statuses, job_id: u16, failed: Option<usize>) {
+    let Some(failed) = failed else {
         return;
+    };
+
+    update_status(statuses, job_id, |status| {
+        status.last_sync = Some(Local::now().format(LOG_STAMP_FORMAT).to_string());
+        status.syncs += 1;
+        status.errors += failed as u32;
+    });
+}
+
+// One journal entry per batch: a single result for everything synced, one per failed path
+fn sync_batch(job: &Job, batch: HashMap<PathBuf, SyncAction>) -> Option<usize> {
+    if batch.is_empty() {
+        return None;
+    }
+
+    // The same paths the job's queue and reconcile() work with
+    let source = normalise_path(&job.source);
+    let target = normalise_path(&job.target);
+
+    run_guarded(job, |cancel, success_directories, failed_directories| {
+        let (mut copied, mut renamed, mut updated, mut deleted, mut kept) = (0, 0, 0, 0, 0);
+        // None outside the source, which the queue never lets through
+        let in_target = |path: &Path| {
+            path.strip_prefix(&source)
+                .ok()
+                .map(|files_names| target.join(files_names))
+        };
+
+        // Renames first, so a path reused right after its rename is not renamed along.
+        // Parents before their children, so a deleted folder goes as a whole
+        let mut batch: Vec<(PathBuf, SyncAction)> = batch.into_iter().collect();
+        batch.sort_by(|(a, a_action), (b, b_action)| {
+            let is_rename = |action: &SyncAction| matches!(action, SyncAction::Rename(_));
+            is_rename(b_action).cmp(&is_rename(a_action)).then(a.cmp(b))
+        });
+
+        for (path, action) in batch {
+            let Some(dest_path) = in_target(&path) else {
+                failed_directories.push(LogResult::new(
+                    REAL_TIME,
+                    &format!(
+                        "{}: not in the source [{}]",
+                        path.display(),
+                        source.display()
+                    ),
+                    job,
+                ));
+                continue;
+            };
+            // Where a renamed path's old copy sits
+            let old_path = match &action {
+                SyncAction::Rename(from) => in_target(from),
+                _ => None,
+            };
+
+            let action = match action {
+                SyncAction::Refresh if path.exists() => SyncAction::Copy,
+                SyncAction::Refresh => SyncAction::Delete,
+                action => action,
+            };
+
+            let result = match &action {
+                // Gone again before the events settled (e.g. an editor's temporary file)
+                SyncAction::Copy if !path.exists() => continue,
+                // Copy or overwrite from path to dest_path
+                SyncAction::Copy => copy_dir(
+                    &path,
+                    &dest_path,
+                    0,
+                    1,
+                    &mut (1 as usize),
+                    cancel,
+                    Some(&target),
+                )
+                .and_then(|_| sync_metadata(&path, &dest_path))
+                .map(|_| copied += 1),
+                // Additive jobs keep what was deleted in the source
+                SyncAction::Delete if job.mirror == 0 => {
+                    kept += 1;
+                    continue;
+                }
+                SyncAction::Delete if dest_path.exists() => {
+                    remove_from_target(&target, &dest_path).map(|_| deleted += 1)
+                }
+                SyncAction::Delete => continue,
+                SyncAction::Metadata if !path.exists() => continue,
+                // Not in the target yet: there is more to it than metadata
+                SyncAction::Metadata if !dest_path.exists() => {
+                    copy_dir(&path, &dest_path, 0, 1, &mut 1, cancel, Some(&target))
+                        .and_then(|_| sync_metadata(&path, &dest_path))
+                        .map(|_| copied += 1)
+                }
+                SyncAction::Metadata => sync_metadata(&path, &dest_path).map(|_| updated += 1),
+                // Renamed again or deleted before the events settled: their own events say so
+                SyncAction::Rename(_) if !path.exists() => continue,
+                // Then brings it up to date, it may have changed since
+                SyncAction::Rename(_)
+                    if old_path.as_ref().is_some_and(|old| old.exists()) && !dest_path.exists() =>
+                {
+                    rename_in_target(old_path.as_ref().unwrap(), &dest_path)
+                        .and_then(|_| {
+                            copy_dir(
+                                &path,
+                                &dest_path,
+                                job.mirror,
+                                0,
+                                &mut 0,
+                                cancel,
+                                Some(&target),
+                            )
+                        })
+                        .and_then(|_| sync_metadata(&path, &dest_path))
+                        .map(|_| renamed += 1)
+                }
+                // Renamed over an existing path, or its old copy is not in the target: copy it as
+                // a new one, a mirrored job drops the old name. What it replaced may look newer
+                SyncAction::Rename(from) => {
+                    let replaced = if *from != path && dest_path.exists() {
+                        remove_from_target(&target, &dest_path)
+                    } else {
+                        Ok(())
+                    };
+                    replaced
+                        .and_then(|_| {
+                            copy_dir(
+                                &path,
+                                &dest_path,
+                                job.mirror,
+                                0,
+                                &mut 0,
+                                cancel,
+                                Some(&target),
+                            )
+                        })
+                        .and_then(|_| sync_metadata(&path, &dest_path))
+                        .map(|_| copied += 1)
+                        .and_then(|_| match &old_path {
+                            Some(old) if job.mirror == 1 && !from.exists() && old.exists() => {
+                                remove_from_target(&target, old)
+                            }
+                            _ => Ok(()),
+                        })
+                }
+                // Resolved above
+                SyncAction::Refresh => continue,
+            };
+
+            if let Err(error) = result {
+                failed_directories.push(LogResult::new(
+                    REAL_TIME,
+                    &format!("{}: {}", path.display(), error),
+                    job,
+                ));
+            }
+        }
+
+        if copied + renamed + updated + deleted + kept > 0 {
+            success_directories.push(LogResult::new(
+                REAL_TIME,
+                &format!(
+                    "OK ({} copied, {} renamed, {} updated, {} deleted, {} deletions ignored)",
+                    copied, renamed, updated, deleted, kept
+                ),
+                job,
+            ));
+        }
+    })
+}
+
+// Brings the whole target in line with the source, following the job's mirror flag
+fn reconcile(job: &Job, reason: &str) -> Option<usize> {
+    let source = normalise_path(&job.source);
+    let target = normalise_path(&job.target);
+
+    println!(
+        "🔄 Reconciling job [{}] because {}",
+        job.id.unwrap(),
+        reason
+    );
+
+    run_guarded(
+        job,
+        |cancel, success_directories, failed_directories| match copy_dir(
+            &source,
+            &target,
+            job.mirror,
+            0,
+            &mut 0,
+            cancel,
+            // Orphans go the way the watcher's own deletions do
+            Some(&target),
+        ) {
+            Ok(fixed) => success_directories.push(LogResult::new(
+                REAL_TIME,
+                &format!("OK ({}, {} drifted files fixed)", reason, fixed),
+                job,
+            )),
+            Err(error) => failed_directories.push(LogResult::new(REAL_TIME, &error, job)),
+        },
+    )
+}
+
+// Runs `sync` like syncrab_b runs a job: unless a policy blocks it, under the job's lock,
+// into a guarded target, then journals what it reported. Returns how many paths failed
+fn run_guarded(
+    job: &Job,
+    sync: impl FnOnce(&CancelToken, &mut Vec<LogResult>, &mut Vec<LogResult>),
+) -> Option<usize> {
+    let mut conn = init_db();
+
+    // Blocked by a blackout window or a pause: syncrab_b runs the whole job once it lifts
+    if let Some(until) = blocked_until(&get_policies(&conn), &Local::now().naive_local()) {
+        defer_run(&mut conn, job.id.unwrap(), &until);
+        return None;
     }
 
     let log = Log::new();
@@ -213,60 +1286,279 @@ fn sync_file_event(event: &Event, job: &Job) {
     let mut success_directories: Vec<LogResult> = Vec::new();
     let mut failed_directories: Vec<LogResult> = Vec::new();
 
-    // Clone once before loop to avoid repeated cloning
-    let frequency = REAL_TIME;
-    let source = job.source.to_string();
-    let target = job.target.to_string();
+    // Wait for any syncrab_b run of this job to finish before touching its target
+    let lock = match JobLock::acquire(job.id.unwrap(), None) {
+        Ok(lock) => lock,
+        Err(LockError::Busy(error) | LockError::Failed(error)) => {
+            failed_directories.push(LogResult::new(REAL_TIME, &error, job));
+            log_results(&mut conn, log, success_directories, failed_directories);
+            return Some(1);
+        }
+    };
 
-    for path in &event.paths {
-        let files_names = path.to_str().unwrap().replace(&source, "");
-        let files_names = files_names.strip_prefix('/').unwrap_or(&files_names);
-        let dest_path = Path::new(&target).join(files_names);
+    // The drive may have been unplugged since the watcher started
+    if let Err(error) = check_target_guard(job, &normalise_path(&job.target)) {
+        failed_directories.push(LogResult::new(REAL_TIME, &error, job));
+        log_results(&mut conn, log, success_directories, failed_directories);
+        return Some(1);
+    }
 
-        match event.kind {
-            // Sync into target (create/update/move in)
-            Create(File) | Create(Folder) | Modify(Data(Any)) | Modify(Name(To)) => {
-                // Copy or overwrite from path to dest_path
-                match copy_dir(&path, &dest_path, 0, 1, &mut (1 as usize)) {
-                    Ok(_) => {
-                        success_directories.push(LogResult::new(frequency, "OK", &source, &target))
-                    }
-                    Err(error) => {
-                        failed_directories.push(LogResult::new(frequency, &error, &source, &target))
-                    }
+    let cancel = CancelToken::new(job.id.unwrap(), lock, None);
+    sync(&cancel, &mut success_directories, &mut failed_directories);
+
+    let failed = failed_directories.len();
+    log_results(&mut conn, log, success_directories, failed_directories);
+    Some(failed)
+}
+
+// Workers ───────────────────────────────────────────────────────
+
+enum Work {
+    Batch(HashMap<PathBuf, SyncAction>),
+    Reconcile(String),
+}
+
+struct Task {
+    token: u64,
+    job: Job,
+    work: Work,
+}
+
+// `count` threads share the syncs of all the jobs, each one tells the dispatcher when it is done
+fn start_workers(count: usize, tx: Sender<Message>) -> Sender<Task> {
+    let (task_tx, task_rx) = channel::<Task>();
+    let task_rx = Arc::new(Mutex::new(task_rx));
+
+    for _ in 0..count {
+        let task_rx = Arc::clone(&task_rx);
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            loop {
+                // The lock is only held while waiting for a task, not while syncing it
+                let task = task_rx.lock().unwrap().recv();
+                let Ok(Task { token, job, work }) = task else {
+                    return;
                 };
+
+                let failed = match work {
+                    Work::Batch(batch) => sync_batch(&job, batch),
+                    Work::Reconcile(reason) => reconcile(&job, &reason),
+                };
+                let _ = tx.send(Message::Done(token, failed));
             }
-            // Delete from target (delete/move out)
-            Modify(Name(From)) => {
-                // Delete dest_path
-                if dest_path.is_dir() {
-                    match remove_dir_all(dest_path) {
-                        Ok(_) => success_directories
-                            .push(LogResult::new(frequency, "OK", &source, &target)),
-                        Err(error) => failed_directories.push(LogResult::new(
-                            frequency,
-                            &error.to_string(),
-                            &source,
-                            &target,
-                        )),
-                    };
-                } else if dest_path.is_file() {
-                    match remove_file(dest_path) {
-                        Ok(_) => success_directories
-                            .push(LogResult::new(frequency, "OK", &source, &target)),
-                        Err(error) => failed_directories.push(LogResult::new(
-                            frequency,
-                            &error.to_string(),
-                            &source,
-                            &target,
-                        )),
-                    };
-                }
-            }
-            _ => {}
+        });
+    }
+
+    task_tx
+}
+
+// Shutdown ──────────────────────────────────────────────────────
+
+// Set once syncrab_w is stopping: no job gets (re)watched and no heartbeat gets written
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+fn handle_signals(watchers: Watchers, socket: PathBuf) {
+    let mut signals = match Signals::new([SIGTERM, SIGINT]) {
+        Ok(signals) => signals,
+        Err(e) => {
+            eprintln!("❌ Failed to listen for stop signals because [{}]", e);
+            return;
+        }
+    };
+
+    let Some(signal) = signals.forever().next() else {
+        return;
+    };
+    let signal = match signal {
+        SIGINT => "SIGINT",
+        _ => "SIGTERM",
+    };
+
+    shutdown(signal, &watchers, &socket);
+    process::exit(0);
+}
+
+// Unwatches every job and lets the workers sync what it already collected, cancels the syncs
+// still running after SHUTDOWN_GRACE_SECS, then journals how the shutdown went
+fn shutdown(signal: &str, watchers: &Watchers, socket: &Path) {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+    println!(
+        "🛑 Stopping because of {}, syncing pending changes...",
+        signal
+    );
+
+    let _ = remove_file(socket);
+
+    let (ack_tx, ack_rx) = channel();
+    let stopped: Vec<Job> = {
+        let mut hub = watchers.lock().unwrap();
+        let Hub { jobs, tx, .. } = &mut *hub;
+        jobs.drain()
+            .map(|(_, watched_job)| stop_watching(tx, watched_job, Some(ack_tx.clone())))
+            .collect()
+    };
+
+    let mut done: Vec<u16> = Vec::new();
+    wait_for_acks(
+        &ack_rx,
+        &mut done,
+        stopped.len(),
+        Duration::from_secs(SHUTDOWN_GRACE_SECS),
+    );
+
+    // Still copying: stop after the current file rather than leave one half written
+    let cancelled: Vec<u16> = stopped
+        .iter()
+        .filter_map(|job| job.id)
+        .filter(|job_id| !done.contains(job_id))
+        .filter(|job_id| request_cancel(*job_id).is_ok())
+        .collect();
+
+    wait_for_acks(
+        &ack_rx,
+        &mut done,
+        stopped.len(),
+        Duration::from_secs(SHUTDOWN_CANCEL_SECS),
+    );
+
+    let mut success_directories: Vec<LogResult> = Vec::new();
+    let mut failed_directories: Vec<LogResult> = Vec::new();
+    let mut running = 0;
+
+    for job in &stopped {
+        let message = format!(
+            "{} Stopped watching because syncrab_w got {}",
+            NOTE_PREFIX, signal
+        );
+        if !done.contains(&job.id.unwrap()) {
+            running += 1;
+            failed_directories.push(LogResult::new(
+                REAL_TIME,
+                &format!("{}, its sync was still running", message),
+                job,
+            ));
+        } else if cancelled.contains(&job.id.unwrap()) {
+            failed_directories.push(LogResult::new(
+                REAL_TIME,
+                &format!(
+                    "{}, its sync was cancelled after {}s",
+                    message, SHUTDOWN_GRACE_SECS
+                ),
+                job,
+            ));
+        } else {
+            success_directories.push(LogResult::new(REAL_TIME, &message, job));
         }
     }
 
+    // Not about one job: it comes first in the journal
+    success_directories.insert(
+        0,
+        LogResult {
+            log_id: None,
+            job_id: None,
+            frequency: REAL_TIME.into(),
+            message: format!(
+                "{} syncrab_w stopped because of {}: {} jobs unwatched, {} cancelled, {} still running",
+                NOTE_PREFIX,
+                signal,
+                stopped.len(),
+                cancelled.len(),
+                running
+            ),
+            source: String::new(),
+            target: String::new(),
+        },
+    );
+
     let mut conn = init_db();
-    log_results(&mut conn, log, success_directories, failed_directories);
+    log_results(
+        &mut conn,
+        Log::new(),
+        success_directories,
+        failed_directories,
+    );
+
+    // The TUI shows the watcher as down right away
+    if let Err(error) = delete_heartbeat(&mut conn) {
+        eprintln!("{}", error);
+    }
+    if let Err(error) = delete_watch_states(&mut conn, None) {
+        eprintln!("{}", error);
+    }
+
+    println!("👋 Stopped");
+}
+
+// Collects the ids of the jobs done syncing, until `count` are or `timeout` went by
+fn wait_for_acks(acks: &Receiver<u16>, done: &mut Vec<u16>, count: usize, timeout: Duration) {
+    let deadline = Instant::now() + timeout;
+    while done.len() < count {
+        match acks.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(job_id) => done.push(job_id),
+            Err(_) => return,
+        }
+    }
+}
+
+// Control ───────────────────────────────────────────────────────
+
+// Answers `syncrab_w ctl` & the TUI, one request per connection
+fn serve_control(listener: UnixListener, watchers: Watchers, statuses: Statuses) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+
+        let timeout = Some(Duration::from_millis(CONTROL_TIMEOUT_MS));
+        let _ = stream.set_read_timeout(timeout);
+        let _ = stream.set_write_timeout(timeout);
+
+        let response = match read_message::<Request>(&stream) {
+            Ok(request) => handle_control(request, &watchers, &statuses),
+            Err(message) => Response::Error { message },
+        };
+        let _ = write_message(&stream, &response);
+    }
+}
+
+fn handle_control(request: Request, watchers: &Watchers, statuses: &Statuses) -> Response {
+    let (job_id, wake) = match request {
+        Request::Status => {
+            let mut jobs: Vec<WatchStatus> = statuses.lock().unwrap().values().cloned().collect();
+            jobs.sort_by_key(|status| status.job_id);
+            return Response::Status { jobs };
+        }
+        Request::Pause { job_id } => (job_id, None),
+        Request::Resume { job_id } => (job_id, Some(Message::Wake)),
+        Request::Resync { job_id } => (job_id, Some(Message::Resync(job_id))),
+    };
+
+    let Some(was_paused) = statuses.lock().unwrap().get_mut(&job_id).map(|status| {
+        let was_paused = status.paused;
+        match wake {
+            None => status.paused = true,
+            Some(Message::Wake) => status.paused = false,
+            Some(_) => {}
+        }
+        was_paused
+    }) else {
+        return Response::Error {
+            message: format!("❌ Job [{}] is not being watched", job_id),
+        };
+    };
+
+    let message = match (&wake, was_paused) {
+        (None, _) => format!("⏸️ Job [{}] is paused, its changes queue up", job_id),
+        (Some(Message::Wake), _) => format!("▶️ Job [{}] is resumed", job_id),
+        (Some(_), true) => format!("🔄 Job [{}] will resync once resumed", job_id),
+        (Some(_), false) => format!("🔄 Job [{}] is resyncing", job_id),
+    };
+
+    // Wakes the dispatcher up
+    if let Some(wake) = wake {
+        let _ = watchers.lock().unwrap().tx.send(wake);
+    }
+
+    Response::Done { message }
 }